@@ -2,6 +2,7 @@ use std::path::PathBuf;
 use strum_macros::Display;
 
 use clap::{Parser, Subcommand, ValueEnum};
+use sqruff_lib::core::linter::cache::LintCache;
 
 use crate::github_action::is_in_github_action;
 
@@ -47,6 +48,20 @@ pub(crate) struct LintArgs {
     pub paths: Vec<PathBuf>,
     #[arg(default_value_t, short, long)]
     pub format: Format,
+    /// Include the complexity metrics of each statement in the output. Only
+    /// supported with `--format json`.
+    #[arg(long)]
+    pub metrics: bool,
+    /// Fail if there are more warnings than this, as well as on any error.
+    #[arg(long)]
+    pub max_warnings: Option<usize>,
+    /// Lint every file again, rather than reusing the results of earlier runs
+    /// for unchanged files.
+    #[arg(long)]
+    pub no_cache: bool,
+    /// Where to cache the results of linting files.
+    #[arg(long, default_value = LintCache::DEFAULT_DIR)]
+    pub cache_dir: PathBuf,
 }
 
 #[derive(Debug, Parser)]
@@ -56,6 +71,10 @@ pub(crate) struct FixArgs {
     /// Skip the confirmation prompt and go straight to applying fixes.
     #[arg(long)]
     pub force: bool,
+    /// Apply fixes to files with parsing errors too, as with
+    /// `fix_even_unparsable`. This can corrupt them.
+    #[arg(long)]
+    pub force_unparsable: bool,
     #[arg(default_value_t, short, long)]
     pub format: Format,
 }
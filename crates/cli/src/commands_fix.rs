@@ -2,7 +2,7 @@ use crate::check_user_input;
 use crate::commands::FixArgs;
 use crate::commands::Format;
 use crate::linter;
-use sqruff_lib::core::config::FluffConfig;
+use sqruff_lib::core::config::{FluffConfig, Value};
 use std::path::Path;
 
 pub(crate) fn run_fix(
@@ -15,8 +15,10 @@ pub(crate) fn run_fix(
         paths,
         force,
         format,
+        force_unparsable,
     } = args;
-    let mut linter = linter(config, format, collect_parse_errors);
+    let config = fix_config(config, force_unparsable);
+    let mut linter = linter(config, format, false, collect_parse_errors);
     let result = linter.lint_paths(paths, true, &ignorer);
 
     if result
@@ -46,17 +48,23 @@ pub(crate) fn run_fix(
             }
         }
 
-        let any_unfixable_errors = result.paths.iter().any(|path| {
-            path.files
-                .iter()
-                .any(|file| !file.get_violations(Some(false)).is_empty())
+        let mut any_unfixable_errors = result.paths.iter().any(|path| {
+            path.files.iter().any(|file| {
+                file.get_violations(Some(false))
+                    .iter()
+                    .any(|violation| violation.is_error())
+            })
         });
 
         for linted_dir in result.paths {
-            for mut file in linted_dir.files {
-                let path = std::mem::take(&mut file.path);
-                let write_buff = file.fix_string();
-                std::fs::write(path, write_buff).unwrap();
+            for file in linted_dir.files {
+                if let Some(reason) = &file.fix_skipped {
+                    eprintln!("Unable to fix {}: {reason}", file.path);
+                    any_unfixable_errors = true;
+                } else if let Err(error) = file.persist_tree() {
+                    eprintln!("Unable to fix {}: {error}", file.path);
+                    any_unfixable_errors = true;
+                }
             }
         }
         linter.formatter_mut().unwrap().completion_message();
@@ -72,21 +80,38 @@ pub(crate) fn run_fix(
 pub(crate) fn run_fix_stdin(
     config: FluffConfig,
     format: Format,
+    force_unparsable: bool,
     collect_parse_errors: bool,
 ) -> i32 {
     let read_in = crate::stdin::read_std_in().unwrap();
 
-    let linter = linter(config, format, collect_parse_errors);
+    let config = fix_config(config, force_unparsable);
+    let linter = linter(config, format, false, collect_parse_errors);
     let result = linter.lint_string(&read_in, None, true);
 
     // print fixed to std out
     let violations = result.get_violations(Some(false));
     println!("{}", result.fix_string());
 
+    if let Some(reason) = &result.fix_skipped {
+        eprintln!("Unable to fix {}: {reason}", result.path);
+        return 1;
+    }
+
     // if all fixable violations are fixable, return 0 else return 1
-    if violations.is_empty() {
+    if !violations.iter().any(|violation| violation.is_error()) {
         0
     } else {
         1
     }
 }
+
+/// The config to fix with, which `--force-unparsable` makes fix files with
+/// parsing errors too.
+fn fix_config(config: FluffConfig, force_unparsable: bool) -> FluffConfig {
+    if force_unparsable {
+        config.with_core_value("fix_even_unparsable", Value::Bool(true))
+    } else {
+        config
+    }
+}
@@ -1,6 +1,9 @@
 use crate::commands::{Format, LintArgs};
 use crate::linter;
 use sqruff_lib::core::config::FluffConfig;
+use sqruff_lib::core::linter::cache::LintCache;
+use sqruff_lib::core::linter::linted_file::LintedFile;
+use sqruff_lib_core::errors::Severity;
 use std::path::Path;
 
 pub(crate) fn run_lint(
@@ -9,13 +12,24 @@ pub(crate) fn run_lint(
     ignorer: impl Fn(&Path) -> bool + Send + Sync,
     collect_parse_errors: bool,
 ) -> i32 {
-    let LintArgs { paths, format } = args;
-    let mut linter = linter(config, format, collect_parse_errors);
+    let LintArgs {
+        paths,
+        format,
+        metrics,
+        max_warnings,
+        no_cache,
+        cache_dir,
+    } = args;
+    let mut linter = linter(config, format, metrics, collect_parse_errors);
+    if !no_cache {
+        linter = linter.with_cache(LintCache::new(cache_dir));
+    }
 
-    linter.lint_paths(paths, false, &ignorer);
+    let result = linter.lint_paths(paths, false, &ignorer);
+    let warnings = count_warnings(result.paths.iter().flat_map(|path| path.files.iter()));
 
     linter.formatter().unwrap().completion_message();
-    if linter.formatter().unwrap().has_fail() {
+    if linter.formatter().unwrap().has_fail() || max_warnings.is_some_and(|max| warnings > max) {
         1
     } else {
         0
@@ -25,18 +39,30 @@ pub(crate) fn run_lint(
 pub(crate) fn run_lint_stdin(
     config: FluffConfig,
     format: Format,
+    metrics: bool,
+    max_warnings: Option<usize>,
     collect_parse_errors: bool,
 ) -> i32 {
     let read_in = crate::stdin::read_std_in().unwrap();
 
-    let linter = linter(config, format, collect_parse_errors);
-    linter.lint_string(&read_in, None, false);
+    let linter = linter(config, format, metrics, collect_parse_errors);
+    let result = linter.lint_string(&read_in, None, false);
+    let warnings = count_warnings([&result].into_iter());
 
     linter.formatter().unwrap().completion_message();
 
-    if linter.formatter().unwrap().has_fail() {
+    if linter.formatter().unwrap().has_fail() || max_warnings.is_some_and(|max| warnings > max) {
         1
     } else {
         0
     }
 }
+
+/// The number of violations which are warnings, which only fail linting past
+/// `--max-warnings`.
+fn count_warnings<'a>(files: impl Iterator<Item = &'a LintedFile>) -> usize {
+    files
+        .flat_map(|file| &file.violations)
+        .filter(|violation| !violation.ignore && violation.severity == Severity::Warning)
+        .count()
+}
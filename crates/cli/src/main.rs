@@ -50,7 +50,7 @@ fn main() {
     let cli = Cli::parse();
     let collect_parse_errors = cli.parsing_errors;
 
-    let config: FluffConfig = if let Some(config) = cli.config.as_ref() {
+    let config = if let Some(config) = cli.config.as_ref() {
         if !Path::new(config).is_file() {
             eprintln!(
                 "The specified config file '{}' does not exist.",
@@ -60,11 +60,52 @@ fn main() {
             std::process::exit(1);
         };
         let read_file = std::fs::read_to_string(config).unwrap();
-        FluffConfig::from_source(&read_file, None)
+        let path = Path::new(config);
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            let pyproject = path
+                .file_name()
+                .is_some_and(|name| name == "pyproject.toml");
+            FluffConfig::from_toml_source(&read_file, None, pyproject)
+        } else {
+            Ok(FluffConfig::from_source(&read_file, None))
+        }
     } else {
-        FluffConfig::from_root(None, false, None).unwrap()
+        FluffConfig::from_root(None, false, None)
+    };
+
+    let config: FluffConfig = match config {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
     };
 
+    if let Err(error) = config.catalog() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+
+    if let Err(error) = config.plugins() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+
+    if let Err(error) = config.pattern_rules() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+
+    if let Err(error) = config.large_file_skip_exempt() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+
+    if let Err(error) = config.severities() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+
     let current_path = std::env::current_dir().unwrap();
     let ignore_file = ignore::IgnoreFile::new_from_root(&current_path).unwrap();
     let ignore_file = Arc::new(ignore_file);
@@ -74,13 +115,23 @@ fn main() {
     };
 
     let status_code = match cli.command {
+        Commands::Lint(args) if args.metrics && !matches!(args.format, Format::Json) => {
+            eprintln!("--metrics is only supported with --format json.");
+            1
+        }
         Commands::Lint(args) => match is_std_in_flag_input(&args.paths) {
             Err(e) => {
                 eprintln!("{e}");
                 1
             }
             Ok(false) => commands_lint::run_lint(args, config, ignorer, collect_parse_errors),
-            Ok(true) => commands_lint::run_lint_stdin(config, args.format, collect_parse_errors),
+            Ok(true) => commands_lint::run_lint_stdin(
+                config,
+                args.format,
+                args.metrics,
+                args.max_warnings,
+                collect_parse_errors,
+            ),
         },
         Commands::Fix(args) => match is_std_in_flag_input(&args.paths) {
             Err(e) => {
@@ -88,7 +139,12 @@ fn main() {
                 1
             }
             Ok(false) => commands_fix::run_fix(args, config, ignorer, collect_parse_errors),
-            Ok(true) => commands_fix::run_fix_stdin(config, args.format, collect_parse_errors),
+            Ok(true) => commands_fix::run_fix_stdin(
+                config,
+                args.format,
+                args.force_unparsable,
+                collect_parse_errors,
+            ),
         },
         Commands::Lsp => {
             sqruff_lsp::run();
@@ -103,7 +159,12 @@ fn main() {
     std::process::exit(status_code);
 }
 
-pub(crate) fn linter(config: FluffConfig, format: Format, collect_parse_errors: bool) -> Linter {
+pub(crate) fn linter(
+    config: FluffConfig,
+    format: Format,
+    metrics: bool,
+    collect_parse_errors: bool,
+) -> Linter {
     let formatter: Arc<dyn Formatter> = match format {
         Format::Human => {
             let output_stream = std::io::stderr().into();
@@ -120,7 +181,10 @@ pub(crate) fn linter(config: FluffConfig, format: Format, collect_parse_errors:
             Arc::new(formatter)
         }
         Format::Json => {
-            let formatter = JsonFormatter::default();
+            let mut formatter = JsonFormatter::default();
+            if metrics {
+                formatter = formatter.with_metrics();
+            }
             Arc::new(formatter)
         }
     };
@@ -34,7 +34,47 @@ fn parse_errors() {
 
     let stdout_str = str::from_utf8(&output.stdout).unwrap();
     let stderr_str = str::from_utf8(&output.stderr).unwrap();
-    assert_eq!(stdout_str, "SelEc\n\n");
-    assert_eq!(stderr_str, "== [<string>] FAIL\nL:   1 | P:   1 | ???? | Unparsable section\nL:   1 | P:   1 | LT12 | Files must end with a single trailing newline.\n                       | [layout.end_of_file]\n");
+    assert_eq!(stdout_str, "SelEc\n");
+    assert_eq!(stderr_str, "== [<string>] FAIL\nL:   1 | P:   1 | ???? | Unparsable section\nL:   1 | P:   1 | LT12 | Files must end with a single trailing newline.\n                       | [layout.end_of_file]\nUnable to fix <string>: Fixes not applied as the file has unparsable sections, which they could corrupt. Set fix_even_unparsable to apply them anyway.\n");
     assert_eq!(output.status.code().unwrap(), 1);
+
+    // STDIN - fix even though unparsable
+    let mut cmd = Command::new(sqruff_path.clone());
+    cmd.env("HOME", PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+    cmd.arg("fix")
+        .arg("-f")
+        .arg("human")
+        .arg("--force-unparsable")
+        .arg("-");
+    cmd.current_dir(cargo_folder);
+    cmd.write_stdin("SelEc");
+
+    let assert = cmd.assert();
+    let output = assert.get_output();
+
+    let stdout_str = str::from_utf8(&output.stdout).unwrap();
+    assert_eq!(stdout_str, "SelEc\n\n");
+    assert_eq!(output.status.code().unwrap(), 0);
+
+    // File - not written unless fixing even though unparsable
+    let path = std::env::temp_dir().join(format!("sqruff-unparsable-{}.sql", std::process::id()));
+    for (force_unparsable, expected) in [(false, "SelEc"), (true, "SelEc\n")] {
+        std::fs::write(&path, "SelEc").unwrap();
+
+        let mut cmd = Command::new(sqruff_path.clone());
+        cmd.env("HOME", PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+        cmd.arg("fix").arg("-f").arg("human").arg("--force");
+        if force_unparsable {
+            cmd.arg("--force-unparsable");
+        }
+        cmd.arg(&path);
+        cmd.current_dir(cargo_folder);
+
+        let assert = cmd.assert();
+        let output = assert.get_output();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+        assert_eq!(output.status.code().unwrap(), i32::from(!force_unparsable));
+    }
+    std::fs::remove_file(path).unwrap();
 }
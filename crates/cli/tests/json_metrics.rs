@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use expect_test::expect_file;
+
+fn main() {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let metrics_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/json_metrics");
+
+    for entry in fs::read_dir(&metrics_dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|e| e.to_str()) != Some("sql") {
+            continue;
+        }
+
+        // Construct the path to the sqruff binary
+        let mut sqruff_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        sqruff_path.push(format!("../../target/{}/sqruff", profile));
+
+        let mut cmd = Command::new(sqruff_path);
+        cmd.arg("lint")
+            .arg(path.to_str().unwrap())
+            .arg("-f")
+            .arg("json")
+            .arg("--metrics");
+        // Set the HOME environment variable to the fake home directory
+        cmd.env("HOME", PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+
+        let assert = cmd.assert();
+
+        let output = assert.get_output();
+        let stderr_str = std::str::from_utf8(&output.stderr).unwrap();
+        let stdout_str = std::str::from_utf8(&output.stdout).unwrap();
+        let exit_code_str = output.status.code().unwrap().to_string();
+
+        let test_dir_str = metrics_dir.to_string_lossy().to_string();
+        let stderr_normalized = stderr_str.replace(&test_dir_str, "tests/json_metrics");
+        let stdout_normalized = stdout_str.replace(&test_dir_str, "tests/json_metrics");
+
+        expect_file![path.with_extension("stderr")].assert_eq(&stderr_normalized);
+        expect_file![path.with_extension("stdout")].assert_eq(&stdout_normalized);
+        expect_file![path.with_extension("exitcode")].assert_eq(&exit_code_str);
+    }
+
+    // Only the JSON output includes metrics.
+    let mut sqruff_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    sqruff_path.push(format!("../../target/{}/sqruff", profile));
+
+    let mut cmd = Command::new(sqruff_path);
+    cmd.arg("lint")
+        .arg(metrics_dir.join("nested_subqueries.sql"))
+        .arg("-f")
+        .arg("human")
+        .arg("--metrics");
+    cmd.env("HOME", PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+
+    let output = cmd.assert().code(1).get_output().clone();
+    assert_eq!(
+        std::str::from_utf8(&output.stderr).unwrap(),
+        "--metrics is only supported with --format json.\n"
+    );
+}
@@ -0,0 +1,14 @@
+WITH eu_customers AS (
+    SELECT id FROM customers WHERE region = 'EU' AND active
+)
+
+SELECT
+    orders.id,
+    CASE WHEN orders.total > 100 THEN 'large' ELSE 'small' END AS size
+FROM orders
+INNER JOIN eu_customers ON orders.customer_id = eu_customers.id
+WHERE orders.product_id IN (
+    SELECT id FROM products WHERE category_id IN (SELECT id FROM categories)
+);
+
+SELECT 1;
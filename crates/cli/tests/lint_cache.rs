@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use assert_cmd::Command;
+
+fn main() {
+    lint_cache();
+}
+
+fn lint_cache() {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+
+    let cargo_folder = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut sqruff_path = PathBuf::from(cargo_folder);
+    sqruff_path.push(format!("../../target/{}/sqruff", profile));
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+    std::fs::write(temp_dir.path().join("query.sql"), "SELECT a  FROM t\n").unwrap();
+    std::fs::write(temp_dir.path().join(".sqruff"), "[sqruff]\nrules = LT01\n").unwrap();
+
+    let lint = |extra_args: &[&str]| {
+        let mut cmd = Command::new(sqruff_path.clone());
+        cmd.env("HOME", PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+        cmd.arg("lint")
+            .arg("-f")
+            .arg("json")
+            .arg("--metrics")
+            .arg("--cache-dir")
+            .arg(&cache_dir)
+            .args(extra_args)
+            .arg("query.sql");
+        cmd.current_dir(temp_dir.path());
+
+        let assert = cmd.assert();
+        let output = assert.get_output();
+        (
+            String::from_utf8(output.stdout.clone()).unwrap(),
+            output.status.code().unwrap(),
+        )
+    };
+    let cache_entries = || {
+        std::fs::read_dir(&cache_dir).map_or(0, |entries| {
+            entries
+                .filter(|entry| {
+                    let path = entry.as_ref().unwrap().path();
+                    path.extension().is_some_and(|ext| ext == "json")
+                })
+                .count()
+        })
+    };
+
+    // Without the cache, nothing is written.
+    let (cold, code) = lint(&["--no-cache"]);
+    assert!(cold.contains("LT01"));
+    assert_eq!(code, 1);
+    assert!(!cache_dir.exists());
+
+    // A warm run reports the same as a cold one.
+    assert_eq!(lint(&[]), (cold.clone(), 1));
+    assert_eq!(cache_entries(), 1);
+    assert_eq!(lint(&[]), (cold.clone(), 1));
+    assert_eq!(cache_entries(), 1);
+
+    // Changing the config file lints the file again.
+    std::fs::write(temp_dir.path().join(".sqruff"), "[sqruff]\nrules = CP01\n").unwrap();
+    let (stdout, code) = lint(&[]);
+    assert!(!stdout.contains("LT01"));
+    assert_eq!(code, 0);
+    assert_eq!(cache_entries(), 2);
+
+    // Entries which haven't been used for a week are removed.
+    let stale_entry = cache_dir.join("stale.json");
+    std::fs::write(&stale_entry, "{}").unwrap();
+    std::fs::File::options()
+        .write(true)
+        .open(&stale_entry)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(8 * 24 * 60 * 60))
+        .unwrap();
+    assert_eq!(cache_entries(), 3);
+    assert_eq!(lint(&[]), (stdout, 0));
+    assert!(!stale_entry.exists());
+    assert_eq!(cache_entries(), 2);
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use expect_test::expect_file;
+
+fn main() {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let severity_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/severity");
+
+    for entry in fs::read_dir(&severity_dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|e| e.to_str()) != Some("sql") {
+            continue;
+        }
+
+        // Construct the path to the sqruff binary
+        let mut sqruff_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        sqruff_path.push(format!("../../target/{}/sqruff", profile));
+
+        let mut cmd = Command::new(sqruff_path);
+        cmd.arg("lint")
+            .arg(path.to_str().unwrap())
+            .arg("-f")
+            .arg("human")
+            .arg("--config")
+            .arg(path.with_extension("cfg"));
+        // Extra arguments, one per line.
+        if let Ok(args) = fs::read_to_string(path.with_extension("args")) {
+            cmd.args(args.lines());
+        }
+        // Set the HOME environment variable to the fake home directory
+        cmd.env("HOME", PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+
+        let assert = cmd.assert();
+
+        let output = assert.get_output();
+        let stderr_str = std::str::from_utf8(&output.stderr).unwrap();
+        let exit_code_str = output.status.code().unwrap().to_string();
+
+        let test_dir_str = severity_dir.to_string_lossy().to_string();
+        let stderr_normalized = stderr_str.replace(&test_dir_str, "tests/severity");
+
+        expect_file![path.with_extension("stderr")].assert_eq(&stderr_normalized);
+        expect_file![path.with_extension("exitcode")].assert_eq(&exit_code_str);
+    }
+}
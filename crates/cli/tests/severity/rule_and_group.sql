@@ -0,0 +1 @@
+select a , b FROM t
@@ -593,6 +593,7 @@ pub enum SyntaxKind {
     MapTypeSchema,
     PrepareStatement,
     ExecuteStatement,
+    ExecuteImmediateStatement,
 }
 
 impl SyntaxKind {
@@ -1,7 +1,9 @@
+use std::borrow::Cow;
 use std::fmt::Display;
 use std::ops::{Deref, DerefMut, Range};
 
 use fancy_regex::Regex;
+use strum_macros::{AsRefStr, EnumString};
 
 use super::parser::segments::base::ErasedSegment;
 use crate::helpers::Config;
@@ -18,17 +20,30 @@ pub trait SqlError {
     fn check_tuple(&self) -> CheckTuple;
 }
 
+/// How serious a violation is. Only errors fail linting.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default, AsRefStr, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+}
+
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct SQLBaseError {
     pub fatal: bool,
     pub ignore: bool,
-    pub warning: bool,
+    pub severity: Severity,
     pub line_no: usize,
     pub line_pos: usize,
     pub description: String,
     pub rule: Option<ErrorStructRule>,
     pub source_slice: Range<usize>,
     pub fixable: bool,
+    /// Whether the violation is in code coming from templating rather than
+    /// the source, reported at the template tag.
+    pub templated: bool,
 }
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -54,6 +69,22 @@ impl SQLBaseError {
     pub fn desc(&self) -> &str {
         &self.description
     }
+
+    /// The description, with a marker when the violation is in templated
+    /// code.
+    pub fn message(&self) -> Cow<'_, str> {
+        if self.templated {
+            format!("{} (in templated code)", self.description).into()
+        } else {
+            Cow::Borrowed(&self.description)
+        }
+    }
+
+    /// Whether this violation fails linting, as opposed to being ignored or
+    /// only a warning or information.
+    pub fn is_error(&self) -> bool {
+        !self.ignore && self.severity == Severity::Error
+    }
 }
 
 impl SqlError for SQLBaseError {
@@ -243,11 +274,18 @@ impl SQLLexError {
             position_marker,
         }
     }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn position_marker(&self) -> &PositionMarker {
+        &self.position_marker
+    }
 }
 
 #[derive(Debug)]
 pub struct SQLFluffSkipFile {
-    #[allow(dead_code)]
     value: String,
 }
 
@@ -256,3 +294,9 @@ impl SQLFluffSkipFile {
         SQLFluffSkipFile { value }
     }
 }
+
+impl Display for SQLFluffSkipFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
@@ -662,18 +662,23 @@ fn iter_segments(
             }
 
             if tfs.slice_type == "literal" {
-                let tfs_offset = tfs.source_slice.start - tfs.templated_slice.start;
+                // The templated file can be longer than the source before this
+                // slice, so the offset is signed.
+                let tfs_offset =
+                    tfs.source_slice.start as isize - tfs.templated_slice.start as isize;
+                let to_source =
+                    |templated_idx: usize| templated_idx.saturating_add_signed(tfs_offset);
 
                 // NOTE: Greater than OR EQUAL, to include the case of it matching
                 // length exactly.
                 if element.template_slice.end <= tfs.templated_slice.end {
                     let slice_start = stashed_source_idx.unwrap_or_else(|| {
-                        element.template_slice.start + consumed_element_length + tfs_offset
+                        to_source(element.template_slice.start + consumed_element_length)
                     });
 
                     result.push(element.to_segment(
                         PositionMarker::new(
-                            slice_start..element.template_slice.end + tfs_offset,
+                            slice_start..to_source(element.template_slice.end),
                             element.template_slice.clone(),
                             templated_file.clone(),
                             None,
@@ -717,8 +722,8 @@ fn iter_segments(
 
                         result.push(element.to_segment(
                             PositionMarker::new(
-                                element.template_slice.start + consumed_element_length + tfs_offset
-                                    ..tfs.templated_slice.end + tfs_offset,
+                                to_source(element.template_slice.start + consumed_element_length)
+                                    ..to_source(tfs.templated_slice.end),
                                 element.template_slice.clone(),
                                 templated_file.clone(),
                                 None,
@@ -152,7 +152,7 @@ impl PositionMarker {
     /// Return the line and position of this marker in the source.
     pub fn source_position(&self) -> (usize, usize) {
         self.templated_file
-            .get_line_pos_of_char_pos(self.templated_slice.start, true)
+            .get_line_pos_of_char_pos(self.source_slice.start, true)
     }
 
     /// Return the line and position of this marker in the source.
@@ -298,7 +298,7 @@ mod tests {
     use std::ops::Range;
 
     use crate::parser::markers::PositionMarker;
-    use crate::templaters::base::TemplatedFile;
+    use crate::templaters::base::{RawFileSlice, TemplatedFile, TemplatedFileSlice};
 
     /// Test that we can correctly infer positions from strings.
     #[test]
@@ -372,6 +372,44 @@ mod tests {
         assert_eq!(pos.working_loc(), (4, 4))
     }
 
+    /// Test that positions in the source are those of the source, for
+    /// untemplated and templated files.
+    #[test]
+    fn test_markers_source_position() {
+        let untemplated: TemplatedFile = "SELECT\n    a\nFROM t\n".into();
+        let pos = PositionMarker::new(13..17, 13..17, untemplated, None, None);
+        assert_eq!(pos.source_position(), (3, 1));
+        assert_eq!(pos.templated_position(), (3, 1));
+
+        // `{{ cols }}` renders over several lines.
+        let templated = TemplatedFile::new(
+            "SELECT\n{{ cols }}\nFROM t\n".into(),
+            "<string>".into(),
+            Some("SELECT\na,\nb\nFROM t\n".into()),
+            Some(vec![
+                TemplatedFileSlice::new("literal", 0..7, 0..7),
+                TemplatedFileSlice::new("templated", 7..17, 7..11),
+                TemplatedFileSlice::new("literal", 17..25, 11..19),
+            ]),
+            Some(vec![
+                RawFileSlice::new("SELECT\n".into(), "literal".into(), 0, None, None),
+                RawFileSlice::new("{{ cols }}".into(), "templated".into(), 7, None, None),
+                RawFileSlice::new("\nFROM t\n".into(), "literal".into(), 17, None, None),
+            ]),
+        )
+        .unwrap();
+
+        // Code in the templated slice is at its tag.
+        let pos = PositionMarker::new(7..17, 10..11, templated.clone(), None, None);
+        assert_eq!(pos.source_position(), (2, 1));
+        assert_eq!(pos.templated_position(), (3, 1));
+
+        // Code after it is at its own source line.
+        let pos = PositionMarker::new(18..22, 12..16, templated, None, None);
+        assert_eq!(pos.source_position(), (3, 1));
+        assert_eq!(pos.templated_position(), (4, 1));
+    }
+
     /// Test that we can correctly compare markers.
     #[test]
     fn test_markers_comparison() {
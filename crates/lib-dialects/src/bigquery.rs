@@ -452,6 +452,34 @@ pub fn dialect() -> Dialect {
             .to_matchable()
             .into(),
         ),
+        (
+            // https://cloud.google.com/bigquery/docs/reference/standard-sql/procedural-language#execute_immediate
+            "ExecuteImmediateStatementSegment".into(),
+            NodeMatcher::new(
+                SyntaxKind::ExecuteImmediateStatement,
+                Sequence::new(vec_of_erased![
+                    Ref::keyword("EXECUTE"),
+                    Ref::keyword("IMMEDIATE"),
+                    Ref::new("ExpressionSegment"),
+                    Sequence::new(vec_of_erased![
+                        Ref::keyword("INTO"),
+                        Delimited::new(vec_of_erased![Ref::new("NakedIdentifierSegment")])
+                    ])
+                    .config(|this| this.optional()),
+                    Sequence::new(vec_of_erased![
+                        Ref::keyword("USING"),
+                        Delimited::new(vec_of_erased![Sequence::new(vec_of_erased![
+                            Ref::new("ExpressionSegment"),
+                            Ref::new("AliasExpressionSegment").optional()
+                        ])])
+                    ])
+                    .config(|this| this.optional())
+                ])
+                .to_matchable(),
+            )
+            .to_matchable()
+            .into(),
+        ),
         (
             "ReturnStatementSegment".into(),
             NodeMatcher::new(
@@ -660,6 +688,7 @@ pub fn dialect() -> Dialect {
                 Ref::new("CreateExternalTableStatementSegment"),
                 Ref::new("AssertStatementSegment"),
                 Ref::new("CallStatementSegment"),
+                Ref::new("ExecuteImmediateStatementSegment"),
                 Ref::new("ReturnStatementSegment"),
                 Ref::new("BreakStatementSegment"),
                 Ref::new("LeaveStatementSegment"),
@@ -160,6 +160,7 @@ GRANTED
 GRANTS
 HOUR
 ILIKE
+IMMEDIATE
 IMPORTED
 IN
 INCREMENT
@@ -6027,6 +6027,24 @@ pub fn raw_dialect() -> Dialect {
             .to_matchable()
             .into(),
         ),
+        (
+            // https://www.postgresql.org/docs/current/sql-execute.html
+            "ExecuteStatementSegment".into(),
+            NodeMatcher::new(
+                SyntaxKind::ExecuteStatement,
+                Sequence::new(vec_of_erased![
+                    Ref::keyword("EXECUTE"),
+                    Ref::new("ObjectReferenceSegment"),
+                    Bracketed::new(vec_of_erased![Delimited::new(vec_of_erased![Ref::new(
+                        "ExpressionSegment"
+                    )])])
+                    .config(|this| this.optional()),
+                ])
+                .to_matchable(),
+            )
+            .to_matchable()
+            .into(),
+        ),
         (
             "DoStatementSegment".into(),
             NodeMatcher::new(
@@ -6746,6 +6764,7 @@ pub fn statement_segment() -> Matchable {
             Ref::new("DropProcedureStatementSegment"),
             Ref::new("CopyStatementSegment"),
             Ref::new("DoStatementSegment"),
+            Ref::new("ExecuteStatementSegment"),
             Ref::new("AlterIndexStatementSegment"),
             Ref::new("ReindexStatementSegment"),
             Ref::new("AlterRoleStatementSegment"),
@@ -1300,6 +1300,7 @@ pub fn dialect() -> Dialect {
                 Ref::new("CreateProcedureStatementSegment"),
                 Ref::new("ScriptingBlockStatementSegment"),
                 Ref::new("ScriptingLetStatementSegment"),
+                Ref::new("ExecuteImmediateStatementSegment"),
                 Ref::new("ReturnStatementSegment"),
                 Ref::new("ShowStatementSegment"),
                 Ref::new("AlterUserStatementSegment"),
@@ -3275,6 +3276,34 @@ pub fn dialect() -> Dialect {
             .to_matchable()
             .into(),
         ),
+        (
+            // https://docs.snowflake.com/en/sql-reference/sql/execute-immediate
+            "ExecuteImmediateStatementSegment".into(),
+            NodeMatcher::new(
+                SyntaxKind::ExecuteImmediateStatement,
+                Sequence::new(vec_of_erased![
+                    Ref::keyword("EXECUTE"),
+                    Ref::keyword("IMMEDIATE"),
+                    one_of(vec_of_erased![
+                        Sequence::new(vec_of_erased![
+                            Ref::new("ColonSegment"),
+                            Ref::new("LocalVariableNameSegment"),
+                        ]),
+                        Ref::new("ExpressionSegment"),
+                    ]),
+                    Sequence::new(vec_of_erased![
+                        Ref::keyword("USING"),
+                        Bracketed::new(vec_of_erased![Delimited::new(vec_of_erased![
+                            Ref::new("LocalVariableNameSegment"),
+                        ])]),
+                    ])
+                    .config(|this| this.optional()),
+                ])
+                .to_matchable(),
+            )
+            .to_matchable()
+            .into(),
+        ),
         (
             "CreateFunctionStatementSegment".into(),
             NodeMatcher::new(
@@ -0,0 +1,9 @@
+EXECUTE IMMEDIATE 'SELECT * FROM orders';
+
+EXECUTE IMMEDIATE 'SELECT COUNT(*) FROM orders WHERE customer_id = ?' INTO order_count USING customer_id;
+
+EXECUTE IMMEDIATE 'SELECT @limit' USING 10 AS `limit`;
+
+EXECUTE IMMEDIATE CONCAT('SELECT * FROM ', table_name);
+
+EXECUTE IMMEDIATE query_text INTO first_value, second_value;
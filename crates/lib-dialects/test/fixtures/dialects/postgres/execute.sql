@@ -0,0 +1,5 @@
+EXECUTE fetch_orders(1, 'open');
+
+EXECUTE fetch_all_orders;
+
+EXECUTE fetch_orders(order_id + 1, lower(status));
@@ -0,0 +1,7 @@
+EXECUTE IMMEDIATE 'SELECT * FROM orders';
+
+EXECUTE IMMEDIATE $query_text;
+
+EXECUTE IMMEDIATE :query_text USING (customer_id, status);
+
+EXECUTE IMMEDIATE 'SELECT * FROM ' || table_name;
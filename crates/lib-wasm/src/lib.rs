@@ -87,18 +87,23 @@ impl Linter {
         } else {
             None
         };
+        // The dialect may be set by the in-file config of the SQL.
+        let dialect = (tool == Tool::Lineage).then(|| {
+            let config = parsed.config.as_deref().unwrap_or(self.base.config());
+            config.get_dialect().clone()
+        });
 
-        let mut result = self.base.lint_parsed(&tables, parsed, tool == Tool::Format);
-        let violations = &mut result.violations;
+        let result = self.base.lint_parsed(&tables, parsed, tool == Tool::Format);
+        let violations = &result.violations;
 
         let diagnostics = violations
-            .iter_mut()
+            .iter()
             .map(|violation| {
                 let start = line_index.line_col(violation.source_slice.start.try_into().unwrap());
                 let end = line_index.line_col(violation.source_slice.end.try_into().unwrap());
 
                 Diagnostic {
-                    message: std::mem::take(&mut violation.description),
+                    message: violation.message().into_owned(),
                     start_line_number: start.line + 1,
                     start_column: start.col + 1,
                     end_line_number: end.line + 1,
@@ -111,7 +116,7 @@ impl Linter {
             Tool::Format => result.fix_string(),
             Tool::Cst => cst.unwrap().stringify(false),
             Tool::Lineage => {
-                let parser = Parser::new(self.base.config().get_dialect(), AHashMap::new());
+                let parser = Parser::new(dialect.as_ref().unwrap(), AHashMap::new());
                 let (tables, node) = Lineage::new(parser, "", sql).build();
 
                 print_tree(&tables, node, "", "", "")
@@ -4,7 +4,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize};
 
 use anstyle::{AnsiColor, Effects, Style};
 use itertools::enumerate;
-use sqruff_lib_core::errors::SQLBaseError;
+use sqruff_lib_core::errors::{SQLBaseError, Severity};
 
 use crate::core::config::FluffConfig;
 use crate::core::linter::linted_file::LintedFile;
@@ -52,9 +52,18 @@ pub trait Formatter: Send + Sync {
 
     fn dispatch_file_violations(&self, linted_file: &LintedFile, only_fixable: bool);
 
+    /// Report a file which wasn't linted, such as for being too large.
+    fn dispatch_skipped_file(&self, f_name: &str, reason: &str);
+
     fn has_fail(&self) -> bool;
 
     fn completion_message(&self);
+
+    /// Whether the complexity metrics of each file are output, so that the
+    /// linter computes them.
+    fn wants_metrics(&self) -> bool {
+        false
+    }
 }
 
 pub struct OutputStreamFormatter {
@@ -83,6 +92,21 @@ impl Formatter for OutputStreamFormatter {
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
     }
 
+    fn dispatch_skipped_file(&self, f_name: &str, reason: &str) {
+        if self.verbosity < 0 {
+            return;
+        }
+
+        let mut text = self.format_filename(f_name, Status::Skip);
+        for line in split_string_on_spaces(reason, self.output_line_length - 4) {
+            text.push_str("\n    ");
+            text.push_str(line);
+        }
+        text.push('\n');
+
+        self.dispatch(&text);
+    }
+
     fn has_fail(&self) -> bool {
         self.has_fail.load(std::sync::atomic::Ordering::SeqCst)
     }
@@ -142,16 +166,21 @@ impl OutputStreamFormatter {
 
         let fails = violations
             .iter()
-            .filter(|violation| !violation.ignore && !violation.warning)
+            .filter(|violation| violation.is_error())
             .count();
         let warns = violations
             .iter()
-            .filter(|violation| violation.warning)
+            .filter(|violation| !violation.ignore && !violation.is_error())
             .count();
         let show = fails + warns > 0;
 
         if self.verbosity > 0 || show {
-            let text = self.format_filename(fname, fails == 0);
+            let status = if fails == 0 {
+                Status::Pass
+            } else {
+                Status::Fail
+            };
+            let text = self.format_filename(fname, status);
             text_buffer.push_str(&text);
             text_buffer.push('\n');
         }
@@ -185,9 +214,7 @@ impl OutputStreamFormatter {
         }
     }
 
-    fn format_filename(&self, filename: &str, success: bool) -> String {
-        let status = if success { Status::Pass } else { Status::Fail };
-
+    fn format_filename(&self, filename: &str, status: Status) -> String {
         let color = match status {
             Status::Pass | Status::Fixed => AnsiColor::Green,
             Status::Fail | Status::Error => {
@@ -195,6 +222,7 @@ impl OutputStreamFormatter {
                     .store(true, std::sync::atomic::Ordering::SeqCst);
                 AnsiColor::Red
             }
+            Status::Skip => AnsiColor::Yellow,
         }
         .on_default();
 
@@ -210,14 +238,16 @@ impl OutputStreamFormatter {
         max_line_length: usize,
     ) -> String {
         let violation: SQLBaseError = violation.into();
-        let desc = violation.desc();
+        let desc = violation.message();
 
         let severity = if violation.ignore {
             "IGNORE: "
-        } else if violation.warning {
-            "WARNING: "
         } else {
-            ""
+            match violation.severity {
+                Severity::Error => "",
+                Severity::Warning => "WARNING: ",
+                Severity::Info => "INFO: ",
+            }
         };
 
         let line_elem = format!("{:4}", violation.line_no);
@@ -232,7 +262,7 @@ impl OutputStreamFormatter {
         }
 
         let split_desc = split_string_on_spaces(&desc, max_line_length - 25);
-        let mut section_color = if violation.ignore || violation.warning {
+        let mut section_color = if !violation.is_error() {
             LIGHT_GREY
         } else {
             AnsiColor::Blue.on_default()
@@ -270,6 +300,7 @@ pub enum Status {
     Fixed,
     Fail,
     Error,
+    Skip,
 }
 
 impl Status {
@@ -279,6 +310,7 @@ impl Status {
             Status::Fixed => "FIXED",
             Status::Fail => "FAIL",
             Status::Error => "ERROR",
+            Status::Skip => "SKIP",
         }
     }
 }
@@ -292,7 +324,7 @@ mod tests {
     use sqruff_lib_core::parser::markers::PositionMarker;
     use sqruff_lib_core::parser::segments::base::SegmentBuilder;
 
-    use super::OutputStreamFormatter;
+    use super::{OutputStreamFormatter, Status};
     use crate::cli::formatters::split_string_on_spaces;
 
     #[test]
@@ -328,7 +360,7 @@ mod tests {
     #[test]
     fn test_cli_formatters_filename_nocol() {
         let formatter = mk_formatter();
-        let actual = formatter.format_filename("blahblah", true);
+        let actual = formatter.format_filename("blahblah", Status::Pass);
 
         assert_eq!(escape_ansi(&actual), "== [blahblah] PASS");
     }
@@ -1,5 +1,6 @@
 use crate::core::config::FluffConfig;
 use crate::core::linter::linted_file::LintedFile;
+use sqruff_lib_core::errors::Severity;
 use std::io::{Stderr, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -45,28 +46,36 @@ impl Formatter for GithubAnnotationNativeFormatter {
             a.line_no
                 .cmp(&b.line_no)
                 .then_with(|| a.line_pos.cmp(&b.line_pos))
-                .then_with(|| {
-                    let b = b.rule.as_ref().unwrap().code;
-                    a.rule.as_ref().unwrap().code.cmp(b)
-                })
+                .then_with(|| a.rule_code().cmp(b.rule_code()))
         });
 
         for violation in violations {
+            let command = match violation.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "notice",
+            };
             let message = format!(
-                "::error title=sqruff,file={},line={},col={}::{}: {}\n",
+                "::{command} title=sqruff,file={},line={},col={}::{}: {}\n",
                 linted_file.path,
                 violation.line_no,
                 violation.line_pos,
-                violation.rule.as_ref().unwrap().code,
-                violation.description
+                violation.rule_code(),
+                violation.message()
             );
             self.dispatch(&message);
-            if !violation.ignore && !violation.warning {
+            if violation.is_error() {
                 self.has_fail.store(true, Ordering::SeqCst);
             }
         }
     }
 
+    fn dispatch_skipped_file(&self, f_name: &str, reason: &str) {
+        self.dispatch(&format!(
+            "::warning title=sqruff,file={f_name}::Skipped: {reason}\n"
+        ));
+    }
+
     fn has_fail(&self) -> bool {
         self.has_fail.load(Ordering::SeqCst)
     }
@@ -4,12 +4,22 @@ use crate::core::{config::FluffConfig, linter::linted_file::LintedFile};
 
 use super::{
     formatters::Formatter,
-    json_types::{Diagnostic, DiagnosticCollection, DiagnosticSeverity},
+    json_types::{Diagnostic, DiagnosticCollection, DiagnosticSeverity, MetricsCollection, Report},
 };
 
 #[derive(Default)]
 pub struct JsonFormatter {
     violations: Mutex<DiagnosticCollection>,
+    metrics: Option<Mutex<MetricsCollection>>,
+}
+
+impl JsonFormatter {
+    /// Also outputs the complexity metrics of each statement, alongside the
+    /// violations.
+    pub fn with_metrics(mut self) -> Self {
+        self.metrics = Some(Mutex::default());
+        self
+    }
 }
 
 impl Formatter for JsonFormatter {
@@ -22,6 +32,21 @@ impl Formatter for JsonFormatter {
                 .map(|err| Diagnostic::from(err.clone()))
                 .collect::<Vec<_>>(),
         );
+        drop(lock);
+
+        if let Some(metrics) = &self.metrics {
+            let mut lock = metrics.lock().unwrap();
+            lock.entry(linted_file.path.clone())
+                .or_default()
+                .extend(linted_file.metrics.iter().cloned());
+        }
+    }
+
+    fn dispatch_skipped_file(&self, f_name: &str, reason: &str) {
+        let mut lock = self.violations.lock().unwrap();
+        lock.entry(f_name.to_owned())
+            .or_default()
+            .push(Diagnostic::skipped(reason.to_owned()));
     }
 
     fn has_fail(&self) -> bool {
@@ -32,9 +57,20 @@ impl Formatter for JsonFormatter {
         })
     }
 
+    fn wants_metrics(&self) -> bool {
+        self.metrics.is_some()
+    }
+
     fn completion_message(&self) {
         let lock = self.violations.lock().unwrap();
-        let json = serde_json::to_string(&*lock).unwrap();
+        let json = match &self.metrics {
+            Some(metrics) => serde_json::to_string(&Report {
+                diagnostics: &lock,
+                metrics: &metrics.lock().unwrap(),
+            }),
+            None => serde_json::to_string(&*lock),
+        }
+        .unwrap();
         println!("{}", json);
     }
 
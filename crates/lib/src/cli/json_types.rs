@@ -1,21 +1,23 @@
 use std::collections::BTreeMap;
 
 use serde::Serialize;
-use sqruff_lib_core::errors::SQLBaseError;
+use sqruff_lib_core::errors::{SQLBaseError, Severity};
+
+use crate::utils::complexity::StatementMetrics;
 
 impl From<SQLBaseError> for Diagnostic {
     fn from(value: SQLBaseError) -> Self {
-        let code = value.rule.map(|rule| rule.code.to_string());
+        let code = value.rule.as_ref().map(|rule| rule.code.to_string());
         Diagnostic {
             range: Range {
                 start: Position::new(value.line_no as u32, value.line_pos as u32),
                 end: Position::new(value.line_no as u32, value.line_pos as u32),
             },
-            message: value.description,
-            severity: if value.warning {
-                DiagnosticSeverity::Warning
-            } else {
-                DiagnosticSeverity::Error
+            message: value.message().into_owned(),
+            severity: match value.severity {
+                Severity::Error => DiagnosticSeverity::Error,
+                Severity::Warning => DiagnosticSeverity::Warning,
+                Severity::Info => DiagnosticSeverity::Information,
             },
             source: Some("sqruff".to_string()),
             code,
@@ -31,6 +33,22 @@ impl From<SQLBaseError> for Diagnostic {
     }
 }
 
+impl Diagnostic {
+    /// The diagnostic of a file which wasn't linted, with the `skipped` code.
+    pub fn skipped(message: String) -> Self {
+        Diagnostic {
+            range: Range {
+                start: Position::new(1, 1),
+                end: Position::new(1, 1),
+            },
+            message,
+            severity: DiagnosticSeverity::Warning,
+            source: Some("sqruff".to_string()),
+            code: Some("skipped".to_string()),
+        }
+    }
+}
+
 /// Represents a line and character position, such as the position of the cursor.
 #[derive(Serialize)]
 struct Position {
@@ -109,3 +127,13 @@ pub enum DiagnosticSeverity {
 }
 
 pub type DiagnosticCollection = BTreeMap<String, Vec<Diagnostic>>;
+
+pub type MetricsCollection = BTreeMap<String, Vec<StatementMetrics>>;
+
+/// The output when metrics are requested, with the diagnostics and the
+/// metrics of each statement by file.
+#[derive(Serialize)]
+pub struct Report<'a> {
+    pub diagnostics: &'a DiagnosticCollection,
+    pub metrics: &'a MetricsCollection,
+}
@@ -1,7 +1,10 @@
+pub mod catalog;
 pub mod config;
 pub mod enums;
 pub mod linter;
 mod parser;
+pub mod pattern;
+pub mod plugin;
 pub mod rules;
 pub mod string_helpers;
 pub mod test_functions;
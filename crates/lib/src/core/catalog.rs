@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use ahash::AHashMap;
+use smol_str::{SmolStr, StrExt};
+use sqruff_lib_core::dialects::base::Dialect;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::helpers::IndexMap;
+use sqruff_lib_core::parser::lexer::StringOrTemplate;
+use sqruff_lib_core::parser::parser::Parser;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, Tables};
+use walkdir::WalkDir;
+
+/// The tables and columns a project is known to contain, used to validate
+/// references in queries.
+///
+/// A catalog is built either from `CREATE TABLE` statements or from a JSON or
+/// YAML file mapping each table name to its list of columns, or to a mapping
+/// of its columns to their types:
+///
+/// ```yaml
+/// orders: [id, customer_id, total]
+/// app.customers:
+///   id: int
+///   name: varchar(100)
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Catalog {
+    tables: IndexMap<SmolStr, CatalogTable>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogTable {
+    pub name: SmolStr,
+    /// Normalised column names and their declared types, or `None` if the
+    /// columns aren't known, as for views and `CREATE TABLE ... AS SELECT`.
+    pub columns: Option<AHashMap<SmolStr, Option<SmolStr>>>,
+}
+
+impl CatalogTable {
+    pub fn has_column(&self, column: &str) -> Option<bool> {
+        self.columns
+            .as_ref()
+            .map(|columns| columns.contains_key(&normalise(column)))
+    }
+
+    /// The declared type of a column, as written in the schema.
+    pub fn column_type(&self, column: &str) -> Option<&str> {
+        self.columns.as_ref()?.get(&normalise(column))?.as_deref()
+    }
+}
+
+/// The columns of a table in a JSON or YAML schema, either as a list of names
+/// or as a mapping of names to types.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum SchemaColumns {
+    Names(Vec<String>),
+    Types(BTreeMap<String, Option<String>>),
+}
+
+impl Catalog {
+    /// Loads a catalog from a JSON or YAML schema file, or from a file or
+    /// directory of DDL parsed with `dialect`.
+    pub fn from_path(path: &Path, dialect: &Dialect) -> Result<Self, String> {
+        let mut catalog = Catalog::default();
+
+        if path.is_dir() {
+            for entry in WalkDir::new(path).sort_by_file_name() {
+                let entry = entry.map_err(|error| error.to_string())?;
+                if entry.file_type().is_file() && is_ddl_file(entry.path()) {
+                    catalog
+                        .add_ddl(&read(entry.path())?, dialect)
+                        .map_err(|error| invalid(entry.path(), error))?;
+                }
+            }
+
+            return Ok(catalog);
+        }
+
+        let source = read(path)?;
+        match path.extension().and_then(|it| it.to_str()) {
+            Some("json") => catalog
+                .add_schema(serde_json::from_str(&source).map_err(|error| invalid(path, error))?),
+            Some("yaml" | "yml") => catalog
+                .add_schema(serde_yaml::from_str(&source).map_err(|error| invalid(path, error))?),
+            _ => catalog
+                .add_ddl(&source, dialect)
+                .map_err(|error| invalid(path, error))?,
+        }
+
+        Ok(catalog)
+    }
+
+    /// Adds the tables and views created by `sql`. SQL which the dialect
+    /// can't lex or parse is an error, as the tables it creates would be
+    /// missing from the catalog.
+    pub fn add_ddl(&mut self, sql: &str, dialect: &Dialect) -> Result<(), String> {
+        let tables = Tables::default();
+        let (tokens, errors) = dialect
+            .lexer()
+            .lex(&tables, StringOrTemplate::String(sql))
+            .map_err(|_| "Unable to lex the DDL".to_string())?;
+        if let Some(error) = errors.first() {
+            return Err(format!(
+                "{} at line {}",
+                error.message(),
+                error.position_marker().line_no()
+            ));
+        }
+        if let Some(unlexable) = tokens.iter().find(|it| it.is_type(SyntaxKind::Unlexable)) {
+            return Err(format!(
+                "Unable to lex characters at line {}",
+                line_no(unlexable)
+            ));
+        }
+
+        let tree = Parser::from(dialect)
+            .parse(&tables, &tokens, None)
+            .map_err(|error| match &error.segment {
+                Some(segment) => format!("{} at line {}", error.description, line_no(segment)),
+                None => error.description,
+            })?;
+        let Some(tree) = tree else {
+            return Ok(());
+        };
+
+        // Code after the last statement the parser matched is kept in a
+        // nested file segment.
+        if let Some(unparsable) = tree
+            .recursive_crawl(
+                const { &SyntaxSet::new(&[SyntaxKind::Unparsable, SyntaxKind::File]) },
+                true,
+                &SyntaxSet::EMPTY,
+                false,
+            )
+            .first()
+        {
+            return Err(format!(
+                "Unparsable section at line {}",
+                line_no(unparsable)
+            ));
+        }
+
+        for statement in tree.recursive_crawl(
+            const {
+                &SyntaxSet::new(&[
+                    SyntaxKind::CreateTableStatement,
+                    SyntaxKind::CreateViewStatement,
+                ])
+            },
+            true,
+            &SyntaxSet::EMPTY,
+            true,
+        ) {
+            let Some(name) =
+                statement.child(const { &SyntaxSet::single(SyntaxKind::TableReference) })
+            else {
+                continue;
+            };
+
+            let columns = statement
+                .child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+                .filter(|_| statement.is_type(SyntaxKind::CreateTableStatement))
+                .map(|bracketed| {
+                    bracketed
+                        .children(const { &SyntaxSet::single(SyntaxKind::ColumnDefinition) })
+                        .filter_map(column_definition)
+                        .collect::<Vec<_>>()
+                });
+
+            self.insert(name.raw(), columns);
+        }
+
+        Ok(())
+    }
+
+    fn add_schema(&mut self, schema: BTreeMap<String, SchemaColumns>) {
+        for (table, columns) in schema {
+            let columns = match columns {
+                SchemaColumns::Names(names) => names.into_iter().map(|it| (it, None)).collect(),
+                SchemaColumns::Types(types) => types.into_iter().collect::<Vec<_>>(),
+            };
+            self.insert(&table, Some(columns));
+        }
+    }
+
+    /// Adds a table with its columns and their types, if known.
+    pub fn insert<S: AsRef<str>>(&mut self, name: &str, columns: Option<Vec<(S, Option<S>)>>) {
+        let columns = columns.map(|columns| {
+            columns
+                .iter()
+                .map(|(column, data_type)| {
+                    (
+                        normalise(column.as_ref()),
+                        data_type.as_ref().map(|it| SmolStr::new(it.as_ref())),
+                    )
+                })
+                .collect()
+        });
+
+        self.tables.insert(
+            normalise(name),
+            CatalogTable {
+                name: name.into(),
+                columns,
+            },
+        );
+    }
+
+    /// Looks up a table by name. A qualified reference also matches an
+    /// unqualified table, and an unqualified reference matches a table in any
+    /// schema.
+    pub fn table(&self, reference: &str) -> Option<&CatalogTable> {
+        let reference = normalise(reference);
+        if let Some(table) = self.tables.get(&reference) {
+            return Some(table);
+        }
+
+        let unqualified = last_part(&reference);
+        self.tables
+            .iter()
+            .find(|(name, _)| {
+                last_part(name) == unqualified && (*name == unqualified || reference == unqualified)
+            })
+            .map(|(_, table)| table)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+}
+
+fn column_definition(definition: &ErasedSegment) -> Option<(SmolStr, Option<SmolStr>)> {
+    let name = definition.segments().iter().find(|it| {
+        matches!(
+            it.get_type(),
+            SyntaxKind::NakedIdentifier | SyntaxKind::QuotedIdentifier | SyntaxKind::Identifier
+        )
+    })?;
+    let data_type = definition
+        .child(const { &SyntaxSet::single(SyntaxKind::DataType) })
+        .map(|it| it.raw().clone());
+
+    Some((name.raw().clone(), data_type))
+}
+
+fn is_ddl_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|it| it.to_str())
+        .is_some_and(|it| it.eq_ignore_ascii_case("sql") || it.eq_ignore_ascii_case("ddl"))
+}
+
+fn invalid(path: &Path, error: impl std::fmt::Display) -> String {
+    format!("Invalid catalog {}: {error}", path.display())
+}
+
+fn line_no(segment: &ErasedSegment) -> usize {
+    segment
+        .get_position_marker()
+        .map_or(1, |marker| marker.line_no())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|error| format!("Unable to read catalog {}: {error}", path.display()))
+}
+
+fn last_part(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Normalises an identifier or dotted reference for case-insensitive
+/// comparison.
+pub fn normalise(name: &str) -> SmolStr {
+    name.replace(['"', '`', '[', ']'], "")
+        .to_uppercase_smolstr()
+}
+
+#[cfg(test)]
+mod tests {
+    use sqruff_lib_core::dialects::init::DialectKind;
+    use sqruff_lib_dialects::kind_to_dialect;
+
+    use super::*;
+
+    #[test]
+    fn ddl_tables_and_columns() {
+        let dialect = kind_to_dialect(&DialectKind::Ansi).unwrap();
+        let mut catalog = Catalog::default();
+        catalog.add_ddl(
+            "CREATE TABLE app.orders (id INT, \"Total\" INT);\nCREATE VIEW big_orders AS SELECT \
+             * FROM app.orders;",
+            &dialect,
+        )
+        .unwrap();
+
+        let orders = catalog.table("orders").unwrap();
+        assert_eq!(orders.name, "app.orders");
+        assert_eq!(orders.has_column("ID"), Some(true));
+        assert_eq!(orders.has_column("total"), Some(true));
+        assert_eq!(orders.has_column("customer_id"), Some(false));
+        assert_eq!(orders.column_type("total"), Some("INT"));
+
+        let view = catalog.table("BIG_ORDERS").unwrap();
+        assert_eq!(view.has_column("id"), None);
+
+        assert!(catalog.table("other.orders").is_none());
+        assert!(catalog.table("customers").is_none());
+    }
+
+    #[test]
+    fn qualified_reference_matches_unqualified_table() {
+        let mut catalog = Catalog::default();
+        catalog.insert("orders", Some(vec![("id", None)]));
+
+        assert!(catalog.table("app.orders").is_some());
+    }
+
+    #[test]
+    fn schema_file_with_column_types() {
+        let dialect = kind_to_dialect(&DialectKind::Ansi).unwrap();
+        let catalog =
+            Catalog::from_path(Path::new("test/fixtures/catalog/types.yml"), &dialect).unwrap();
+
+        let orders = catalog.table("orders").unwrap();
+        assert_eq!(orders.column_type("created_on"), Some("date"));
+        assert_eq!(orders.column_type("missing"), None);
+
+        let customers = catalog.table("customers").unwrap();
+        assert_eq!(customers.has_column("name"), Some(true));
+        assert_eq!(customers.column_type("name"), None);
+    }
+
+    #[test]
+    fn unparsable_ddl() {
+        let dialect = kind_to_dialect(&DialectKind::Ansi).unwrap();
+        let error = Catalog::from_path(Path::new("test/fixtures/catalog/unparsable.sql"), &dialect)
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            "Invalid catalog test/fixtures/catalog/unparsable.sql: Unparsable section at line 3"
+        );
+    }
+}
@@ -1,16 +1,23 @@
 use std::ops::Index;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 
 use ahash::AHashMap;
 use configparser::ini::Ini;
 use itertools::Itertools;
 use sqruff_lib_core::dialects::base::Dialect;
 use sqruff_lib_core::dialects::init::{dialect_readout, DialectKind};
-use sqruff_lib_core::errors::SQLFluffUserError;
+use sqruff_lib_core::errors::{SQLBaseError, SQLFluffUserError, Severity};
 use sqruff_lib_core::parser::parser::Parser;
 use sqruff_lib_dialects::kind_to_dialect;
 
+use crate::core::catalog::Catalog;
+use crate::core::pattern::{load_pattern_rules, PatternRule};
+use crate::core::plugin::{load_plugins, Plugin};
+use crate::core::rules::base::RuleGroups;
+use crate::rules::rules;
+use crate::templaters::TEMPLATERS;
 use crate::utils::reflow::config::ReflowConfig;
 
 /// split_comma_separated_string takes a string and splits it on commas and
@@ -37,6 +44,9 @@ pub struct FluffConfig {
     pub(crate) dialect: Dialect,
     sql_file_exts: Vec<String>,
     reflow: ReflowConfig,
+    catalog: Arc<OnceLock<Result<Option<Catalog>, String>>>,
+    plugins: Arc<OnceLock<Result<Vec<Arc<Plugin>>, String>>>,
+    pattern_rules: Arc<OnceLock<Result<Vec<PatternRule>, String>>>,
 }
 
 impl Default for FluffConfig {
@@ -68,6 +78,18 @@ impl FluffConfig {
         FluffConfig::new(configs, None, None)
     }
 
+    /// from_toml_source creates a config object from the contents of a `pyproject.toml`, if
+    /// `pyproject` is set, or of a `sqruff.toml`.
+    pub fn from_toml_source(
+        source: &str,
+        optional_path_specification: Option<&Path>,
+        pyproject: bool,
+    ) -> Result<FluffConfig, SQLFluffUserError> {
+        let configs =
+            ConfigLoader::from_toml_source(source, optional_path_specification, pyproject)?;
+        Ok(FluffConfig::new(configs, None, None))
+    }
+
     pub fn get_section(&self, section: &str) -> &AHashMap<String, Value> {
         self.raw[section].as_map().unwrap()
     }
@@ -78,33 +100,16 @@ impl FluffConfig {
         extra_config_path: Option<String>,
         indentation: Option<FluffConfigIndentation>,
     ) -> Self {
-        fn nested_combine(
-            mut a: AHashMap<String, Value>,
-            b: AHashMap<String, Value>,
-        ) -> AHashMap<String, Value> {
-            for (key, value_b) in b {
-                match (a.get(&key), value_b) {
-                    (Some(Value::Map(map_a)), Value::Map(map_b)) => {
-                        let combined = nested_combine(map_a.clone(), map_b);
-                        a.insert(key, Value::Map(combined));
-                    }
-                    (_, value) => {
-                        a.insert(key, value);
-                    }
-                }
-            }
-            a
-        }
-
         let values = ConfigLoader::get_config_elems_from_file(
             None,
             include_str!("./default_config.cfg").into(),
-        );
+        )
+        .unwrap();
 
         let mut defaults = AHashMap::new();
         ConfigLoader::incorporate_vals(&mut defaults, values);
 
-        let mut configs = nested_combine(defaults, configs);
+        let mut configs = combine_configs(defaults, configs);
 
         let dialect = match configs
             .get("core")
@@ -124,19 +129,17 @@ impl FluffConfig {
             // Allowlists and denylistsignore_words
             ("exclude_rules", "rule_denylist"),
         ] {
-            match configs["core"].as_map().unwrap().get(in_key) {
-                Some(value) if !value.is_none() => {
-                    let string = value.as_string().unwrap();
-                    let values = split_comma_separated_string(string);
-
-                    configs
-                        .get_mut("core")
-                        .unwrap()
-                        .as_map_mut()
-                        .unwrap()
-                        .insert(out_key.into(), values);
-                }
-                _ => {}
+            // Lists already split for the config this one is based on are kept
+            // as they are.
+            if let Some(Value::String(string)) = configs["core"].as_map().unwrap().get(in_key) {
+                let values = split_comma_separated_string(string);
+
+                configs
+                    .get_mut("core")
+                    .unwrap()
+                    .as_map_mut()
+                    .unwrap()
+                    .insert(out_key.into(), values);
             }
         }
 
@@ -156,6 +159,9 @@ impl FluffConfig {
             indentation: indentation.unwrap_or_default(),
             sql_file_exts,
             reflow: ReflowConfig::default(),
+            catalog: Arc::default(),
+            plugins: Arc::default(),
+            pattern_rules: Arc::default(),
         };
         this.reflow = ReflowConfig::from_fluff_config(&this);
         this
@@ -166,6 +172,15 @@ impl FluffConfig {
         self
     }
 
+    /// This config with a `core` setting overridden, e.g. by a command line
+    /// flag.
+    pub fn with_core_value(mut self, key: &str, value: Value) -> Self {
+        if let Some(core) = self.raw.get_mut("core").and_then(Value::as_map_mut) {
+            core.insert(key.to_string(), value);
+        }
+        self
+    }
+
     /// Loads a config object just based on the root directory.
     // TODO This is not a translation that is particularly accurate.
     pub fn from_root(
@@ -175,7 +190,7 @@ impl FluffConfig {
     ) -> Result<FluffConfig, SQLFluffUserError> {
         let loader = ConfigLoader {};
         let mut config =
-            loader.load_config_up_to_path(".", extra_config_path.clone(), ignore_local_config);
+            loader.load_config_up_to_path(".", extra_config_path.clone(), ignore_local_config)?;
 
         if let Some(overrides) = overrides {
             if let Some(dialect) = overrides.get("dialect") {
@@ -207,20 +222,210 @@ impl FluffConfig {
         }
     }
 
-    /// Process a full raw file for inline config and update self.
-    pub fn process_raw_file_for_config(&self, raw_str: &str) {
-        // Scan the raw file for config commands
-        for raw_line in raw_str.lines() {
-            if raw_line.to_string().starts_with("-- sqlfluff") {
-                // Found an in-file config command
-                self.process_inline_config(raw_line)
+    /// Process a full raw file for in-file config directives, such as
+    /// `-- sqlfluff:dialect:bigquery`. Returns the config of the file, if
+    /// any directive changes it, along with the errors of the invalid
+    /// directives, which are skipped.
+    pub fn process_raw_file_for_config(
+        &self,
+        raw_str: &str,
+    ) -> (Option<FluffConfig>, Vec<SQLBaseError>) {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+
+        let mut offset = 0;
+        for (line_no, raw_line) in raw_str.split_inclusive('\n').enumerate() {
+            let line = raw_line.trim_end_matches(['\r', '\n']);
+            match self.process_inline_config(line) {
+                Ok(Some(value)) => values.push(value),
+                Ok(None) => {}
+                Err(description) => errors.push(SQLBaseError {
+                    fatal: true,
+                    ignore: false,
+                    severity: Severity::Error,
+                    line_no: line_no + 1,
+                    line_pos: 1,
+                    description,
+                    rule: None,
+                    source_slice: offset..offset + line.len(),
+                    fixable: false,
+                    templated: false,
+                }),
+            }
+            offset += raw_line.len();
+        }
+
+        if values.is_empty() {
+            return (None, errors);
+        }
+
+        let mut configs = AHashMap::new();
+        ConfigLoader::incorporate_vals(&mut configs, values);
+
+        (Some(self.overlay(configs)), errors)
+    }
+
+    /// The config for the files in `dir`, with the config files found below
+    /// the working directory on the way to `dir` merged onto this one, the
+    /// nearest last. `None` if there aren't any.
+    pub fn for_directory(&self, dir: &Path) -> Result<Option<FluffConfig>, String> {
+        // The config of the working directory is the one this config is
+        // loaded from, or is replaced by it.
+        let working_path = std::env::current_dir().unwrap().canonicalize().unwrap();
+        let configs = ConfigLoader::iter_config_locations_up_to_path(dir, None, false)
+            .filter(|path| !working_path.starts_with(path))
+            .map(|path| ConfigLoader.load_config_at_path(path))
+            .filter_ok(|configs| !configs.is_empty())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| error.value)?;
+
+        if configs.is_empty() {
+            return Ok(None);
+        }
+
+        let config = self.overlay(nested_combine(configs));
+        config.severities()?;
+        Ok(Some(config))
+    }
+
+    /// A copy of this config with `configs` merged onto it.
+    fn overlay(&self, configs: AHashMap<String, Value>) -> FluffConfig {
+        let mut raw = combine_configs(self.raw.clone(), configs);
+        // The rule lists are worked out again from `rules` and `exclude_rules`.
+        let core = raw.get_mut("core").unwrap().as_map_mut().unwrap();
+        core.remove("rule_allowlist");
+        core.remove("rule_denylist");
+
+        let mut config = FluffConfig::new(
+            raw,
+            self.extra_config_path.clone(),
+            Some(self.indentation.clone()),
+        );
+        // File extensions only apply to the config paths are linted with.
+        config.sql_file_exts.clone_from(&self.sql_file_exts);
+
+        // Extensions are only loaded again if their settings change.
+        let core = |config: &FluffConfig, key: &str| config.raw["core"][key].clone();
+        if core(&config, "plugins") == core(self, "plugins") {
+            config.plugins = self.plugins.clone();
+            if config.raw.get("pattern_rules") == self.raw.get("pattern_rules") {
+                config.pattern_rules = self.pattern_rules.clone();
             }
         }
+        if core(&config, "catalog_path") == core(self, "catalog_path")
+            && config.dialect == self.dialect
+        {
+            config.catalog = self.catalog.clone();
+        }
+
+        config
+    }
+
+    /// Process an inline config command, returning the path and value it
+    /// sets, or `None` if the line isn't a config command.
+    pub fn process_inline_config(
+        &self,
+        config_line: &str,
+    ) -> Result<Option<(Vec<String>, Value)>, String> {
+        let Some(captures) =
+            lazy_regex::regex!(r"^--\s*(?:sqlfluff|sqruff):(.*)$").captures(config_line)
+        else {
+            return Ok(None);
+        };
+
+        let mut path: Vec<String> = captures[1]
+            .split(':')
+            .map(|elem| elem.trim().to_owned())
+            .collect();
+        if path.len() < 2 || path.iter().any(String::is_empty) {
+            return Err(format!(
+                "Malformed in-file config '{}'. Expected '-- sqlfluff:<key>:<value>'",
+                &captures[1]
+            ));
+        }
+
+        let value = path.pop().unwrap();
+        let value: Value = value.parse().unwrap();
+        for elem in &mut path {
+            elem.make_ascii_lowercase();
+        }
+        if path.len() == 1 {
+            path.insert(0, "core".into());
+        }
+
+        self.check_inline_config(&path, &value)?;
+        Ok(Some((path, value)))
     }
 
-    /// Process an inline config command and update self.
-    pub fn process_inline_config(&self, _config_line: &str) {
-        panic!("Not implemented")
+    fn check_inline_config(&self, path: &[String], value: &Value) -> Result<(), String> {
+        let path_str = || path.iter().skip_while(|&elem| elem == "core").join(":");
+        let unknown = || Err(format!("Unknown in-file config '{}'", path_str()));
+
+        let is_known = |section: &[&str], key: &str| {
+            section
+                .iter()
+                .try_fold(&self.raw, |map, &elem| map.get(elem)?.as_map())
+                .and_then(|map| map.get(key))
+                .is_some_and(|value| value.as_map().is_none())
+        };
+
+        let path = path.iter().map(String::as_str).collect_vec();
+        match path.as_slice() {
+            ["core", key @ ("plugins" | "sql_file_exts")] | [key @ "pattern_rules", ..] => {
+                return Err(format!("'{key}' can't be set in a file"));
+            }
+            ["core", "dialect"] => {
+                let dialect = value.as_string().unwrap_or_default();
+                if DialectKind::from_str(dialect)
+                    .ok()
+                    .and_then(|kind| kind_to_dialect(&kind))
+                    .is_none()
+                {
+                    return Err(format!("Unknown dialect '{dialect}'"));
+                }
+            }
+            ["core", "templater"] => {
+                let templater = value.as_string().unwrap_or_default();
+                if !TEMPLATERS.iter().any(|it| it.name() == templater) {
+                    return Err(format!("Unknown templater '{templater}'"));
+                }
+            }
+            ["rules", section, "severity"] => {
+                let is_rule = || {
+                    rules().iter().any(|rule| rule.name() == *section)
+                        || self
+                            .plugins()
+                            .unwrap_or_default()
+                            .iter()
+                            .any(|plugin| plugin.rule().name() == *section)
+                };
+                if RuleGroups::from_str(section).is_err() && !is_rule() {
+                    return unknown();
+                }
+                let severity = value.as_string().unwrap_or_default();
+                if Severity::from_str(severity).is_err() {
+                    return Err(format!(
+                        "Invalid severity '{severity}', expected error, warning or info"
+                    ));
+                }
+            }
+            // Any segment type may be given a layout.
+            ["layout", "type", _, key] => {
+                let known = self.raw["layout"]["type"]
+                    .as_map()
+                    .is_some_and(|types| types.values().any(|it| it[*key] != Value::None));
+                if !known {
+                    return unknown();
+                }
+            }
+            // Templaters have their own options, such as the variables set
+            // in their context.
+            ["templater", name, _, ..] if TEMPLATERS.iter().any(|it| it.name() == *name) => {}
+            [section @ .., key] if is_known(section, key) => {}
+            _ => return unknown(),
+        }
+
+        Ok(())
     }
 
     /// Check if the config specifies a dialect, raising an error if not.
@@ -246,6 +451,98 @@ command. Available dialects: {}",
     pub fn sql_file_exts(&self) -> &[String] {
         self.sql_file_exts.as_ref()
     }
+
+    /// The catalog of known tables and columns set by `catalog_path`, loaded
+    /// on first use.
+    pub fn catalog(&self) -> Result<Option<&Catalog>, &str> {
+        self.catalog
+            .get_or_init(|| match self.raw["core"]["catalog_path"].as_string() {
+                Some(path) => Catalog::from_path(Path::new(path), &self.dialect).map(Some),
+                None => Ok(None),
+            })
+            .as_ref()
+            .map(Option::as_ref)
+            .map_err(String::as_str)
+    }
+
+    /// The rules defined by the WebAssembly modules set by `plugins`, loaded
+    /// on first use.
+    pub fn plugins(&self) -> Result<&[Arc<Plugin>], &str> {
+        self.plugins
+            .get_or_init(|| {
+                let paths = self.raw["core"]["plugins"]
+                    .as_array()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|it| it.as_string().map(|it| it.trim().to_owned()))
+                    .filter(|it| !it.is_empty())
+                    .collect_vec();
+                load_plugins(&paths)
+            })
+            .as_ref()
+            .map(Vec::as_slice)
+            .map_err(String::as_str)
+    }
+
+    /// The glob patterns set by `large_file_skip_exempt`, of the files
+    /// linted whatever their length.
+    pub fn large_file_skip_exempt(&self) -> Result<Vec<glob::Pattern>, String> {
+        self.raw["core"]["large_file_skip_exempt"]
+            .as_array()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|it| it.as_string().map(|it| it.trim().to_owned()))
+            .filter(|it| !it.is_empty())
+            .map(|it| {
+                glob::Pattern::new(&it).map_err(|error| {
+                    format!("Invalid pattern '{it}' in 'large_file_skip_exempt': {error}")
+                })
+            })
+            .collect()
+    }
+
+    /// The `severity` set in each rules section, by the name of the rule or
+    /// group of the section.
+    pub fn severities(&self) -> Result<AHashMap<&str, Severity>, String> {
+        let Some(sections) = self.raw.get("rules").and_then(Value::as_map) else {
+            return Ok(AHashMap::new());
+        };
+
+        sections
+            .iter()
+            .filter_map(|(section, value)| {
+                let severity = value.as_map()?.get("severity")?;
+                if severity.is_none() {
+                    return None;
+                }
+
+                let severity = severity.as_string().unwrap_or_default();
+                let parsed = Severity::from_str(severity).map_err(|_| {
+                    format!(
+                        "Invalid severity '{severity}' for '{section}', expected error, warning \
+                         or info"
+                    )
+                });
+                Some(parsed.map(|it| (section.as_str(), it)))
+            })
+            .collect()
+    }
+
+    /// The rules defined by the `pattern_rules` sections, loaded on first
+    /// use.
+    pub fn pattern_rules(&self) -> Result<&[PatternRule], &str> {
+        self.pattern_rules
+            .get_or_init(|| {
+                let section = self.raw.get("pattern_rules").and_then(Value::as_map);
+                load_pattern_rules(
+                    section.unwrap_or(&AHashMap::new()),
+                    self.plugins().unwrap_or_default(),
+                )
+            })
+            .as_ref()
+            .map(Vec::as_slice)
+            .map_err(String::as_str)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -280,8 +577,8 @@ impl ConfigLoader {
         let common_path = common_path::common_path(&given_path, working_path).unwrap();
         let mut path_to_visit = common_path;
 
-        let head = Some(given_path.canonicalize().unwrap()).into_iter();
-        let tail = std::iter::from_fn(move || {
+        let last = Some(given_path.canonicalize().unwrap()).into_iter();
+        let parents = std::iter::from_fn(move || {
             if path_to_visit != given_path {
                 let path = path_to_visit.canonicalize().unwrap();
 
@@ -324,7 +621,9 @@ impl ConfigLoader {
             }
         });
 
-        head.chain(tail)
+        // From the outermost directory to `path`, so nearer configs take
+        // precedence.
+        parents.chain(last)
     }
 
     pub fn load_config_up_to_path(
@@ -332,30 +631,37 @@ impl ConfigLoader {
         path: impl AsRef<Path>,
         extra_config_path: Option<String>,
         ignore_local_config: bool,
-    ) -> AHashMap<String, Value> {
+    ) -> Result<AHashMap<String, Value>, SQLFluffUserError> {
         let path = path.as_ref();
 
         let config_stack = if ignore_local_config {
             extra_config_path
-                .map(|path| vec![self.load_config_at_path(path)])
+                .map(|path| self.load_config_at_path(path).map(|configs| vec![configs]))
+                .transpose()?
                 .unwrap_or_default()
         } else {
             let configs = Self::iter_config_locations_up_to_path(path, None, ignore_local_config);
             configs
                 .map(|path| self.load_config_at_path(path))
-                .collect_vec()
+                .collect::<Result<Vec<_>, _>>()?
         };
 
-        nested_combine(config_stack)
+        Ok(nested_combine(config_stack))
     }
 
-    pub fn load_config_at_path(&self, path: impl AsRef<Path>) -> AHashMap<String, Value> {
+    pub fn load_config_at_path(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<AHashMap<String, Value>, SQLFluffUserError> {
         let path = path.as_ref();
 
+        // Later files take precedence over earlier ones.
         let filename_options = [
             /* "setup.cfg", "tox.ini", "pep8.ini", */
+            "pyproject.toml",
             ".sqlfluff",
-            ".sqruff", /* "pyproject.toml" */
+            ".sqruff",
+            "sqruff.toml",
         ];
 
         let mut configs = AHashMap::new();
@@ -364,32 +670,141 @@ impl ConfigLoader {
             for fname in filename_options {
                 let path = path.join(fname);
                 if path.exists() {
-                    ConfigLoader::load_config_file(path, &mut configs);
+                    ConfigLoader::load_config_file(path, &mut configs)?;
                 }
             }
         } else if path.is_file() {
-            ConfigLoader::load_config_file(path, &mut configs);
+            ConfigLoader::load_config_file(path, &mut configs)?;
         };
 
-        configs
+        Ok(configs)
     }
 
     pub fn from_source(source: &str, path: Option<&Path>) -> AHashMap<String, Value> {
         let mut configs = AHashMap::new();
-        let elems = ConfigLoader::get_config_elems_from_file(path, Some(source));
+        let elems = ConfigLoader::get_config_elems_from_file(path, Some(source))
+            .unwrap_or_else(|error| panic!("{error}"));
         ConfigLoader::incorporate_vals(&mut configs, elems);
         configs
     }
 
-    pub fn load_config_file(path: impl AsRef<Path>, configs: &mut AHashMap<String, Value>) {
-        let elems = ConfigLoader::get_config_elems_from_file(path.as_ref().into(), None);
+    /// Loads the config from the contents of a TOML file: the `[tool.sqlfluff]`
+    /// and `[tool.sqruff]` tables of a `pyproject.toml` if `pyproject` is set,
+    /// or else the whole file, as in a `sqruff.toml`.
+    pub fn from_toml_source(
+        source: &str,
+        path: Option<&Path>,
+        pyproject: bool,
+    ) -> Result<AHashMap<String, Value>, SQLFluffUserError> {
+        let mut configs = AHashMap::new();
+        let elems = ConfigLoader::get_config_elems_from_toml(path, source, pyproject)?;
+        ConfigLoader::incorporate_vals(&mut configs, elems);
+        Ok(configs)
+    }
+
+    pub fn load_config_file(
+        path: impl AsRef<Path>,
+        configs: &mut AHashMap<String, Value>,
+    ) -> Result<(), SQLFluffUserError> {
+        let path = path.as_ref();
+        let elems = if path.extension().is_some_and(|ext| ext == "toml") {
+            let source = std::fs::read_to_string(path).map_err(|error| {
+                SQLFluffUserError::new(format!("Failed to read {}: {error}", path.display()))
+            })?;
+            let pyproject = path
+                .file_name()
+                .is_some_and(|name| name == "pyproject.toml");
+            ConfigLoader::get_config_elems_from_toml(Some(path), &source, pyproject)?
+        } else {
+            ConfigLoader::get_config_elems_from_file(path.into(), None)?
+        };
         ConfigLoader::incorporate_vals(configs, elems);
+        Ok(())
+    }
+
+    fn get_config_elems_from_toml(
+        path: Option<&Path>,
+        config_string: &str,
+        pyproject: bool,
+    ) -> Result<Vec<(Vec<String>, Value)>, SQLFluffUserError> {
+        fn flatten(
+            table: toml::Table,
+            key: &mut Vec<String>,
+            path: Option<&Path>,
+            buff: &mut Vec<(Vec<String>, Value)>,
+        ) {
+            for (name, value) in table {
+                let name = name.to_lowercase();
+                let value = match value {
+                    toml::Value::Table(table) => {
+                        key.push(name);
+                        flatten(table, key, path, buff);
+                        key.pop();
+                        continue;
+                    }
+                    // Lists are written as in INI files.
+                    toml::Value::Array(values) => values
+                        .into_iter()
+                        .map(|value| match value {
+                            toml::Value::String(value) => value,
+                            value => value.to_string(),
+                        })
+                        .join(","),
+                    toml::Value::String(value) => value,
+                    value => value.to_string(),
+                };
+
+                let value = ConfigLoader::resolve_paths(&name, value.parse().unwrap(), path);
+
+                let mut key = key.clone();
+                // Rule sections are named with dots, such as
+                // `rules.capitalisation.keywords`, which TOML reads as tables.
+                if key.len() > 2 && key[0] == "rules" {
+                    let section = key.split_off(1).join(".");
+                    key.push(section);
+                }
+                // Settings outside of any table are core settings, as in
+                // the `core` table.
+                if key.is_empty() {
+                    key.push("core".to_owned());
+                }
+                key.push(name);
+                buff.push((key, value));
+            }
+        }
+
+        let mut table: toml::Table = config_string.parse().map_err(|error| {
+            let path = path.map_or_else(|| "config".into(), Path::to_string_lossy);
+            SQLFluffUserError::new(format!("Failed to parse {path}: {error}"))
+        })?;
+
+        let tables = if pyproject {
+            let mut tool = match table.remove("tool") {
+                Some(toml::Value::Table(tool)) => tool,
+                _ => toml::Table::new(),
+            };
+            ["sqlfluff", "sqruff"]
+                .into_iter()
+                .filter_map(|name| match tool.remove(name) {
+                    Some(toml::Value::Table(table)) => Some(table),
+                    _ => None,
+                })
+                .collect_vec()
+        } else {
+            vec![table]
+        };
+
+        let mut buff = Vec::new();
+        for table in tables {
+            flatten(table, &mut Vec::new(), path, &mut buff);
+        }
+        Ok(buff)
     }
 
     fn get_config_elems_from_file(
         path: Option<&Path>,
         config_string: Option<&str>,
-    ) -> Vec<(Vec<String>, Value)> {
+    ) -> Result<Vec<(Vec<String>, Value)>, SQLFluffUserError> {
         let mut buff = Vec::new();
         let mut config = Ini::new();
 
@@ -398,10 +813,15 @@ impl ConfigLoader {
                 unimplemented!("One of fpath or config_string is required.")
             }
             (None, Some(text)) => text.to_owned(),
-            (Some(path), None) => std::fs::read_to_string(path).unwrap(),
+            (Some(path), None) => std::fs::read_to_string(path).map_err(|error| {
+                SQLFluffUserError::new(format!("Failed to read {}: {error}", path.display()))
+            })?,
         };
 
-        config.read(content).unwrap();
+        config.read(content).map_err(|error| {
+            let path = path.map_or_else(|| "config".into(), Path::to_string_lossy);
+            SQLFluffUserError::new(format!("Failed to parse {path}: {error}"))
+        })?;
 
         for section in config.sections() {
             let key = if section == "sqlfluff" || section == "sqruff" {
@@ -420,13 +840,7 @@ impl ConfigLoader {
                 for (name, value) in section {
                     let value: Value = value.as_ref().unwrap().parse().unwrap();
                     let name_lowercase = name.to_lowercase();
-
-                    if name_lowercase == "load_macros_from_path" {
-                        unimplemented!()
-                    } else if name_lowercase.ends_with("_path") || name_lowercase.ends_with("_dir")
-                    {
-                        unimplemented!()
-                    }
+                    let value = ConfigLoader::resolve_paths(&name_lowercase, value, path);
 
                     let mut key = key.clone();
                     key.push(name.clone());
@@ -435,7 +849,35 @@ impl ConfigLoader {
             }
         }
 
-        buff
+        Ok(buff)
+    }
+
+    /// Paths are relative to the config file they are set in.
+    fn resolve_paths(name: &str, value: Value, path: Option<&Path>) -> Value {
+        match (value, path.and_then(Path::parent)) {
+            // Comma separated lists of paths.
+            (Value::String(paths), Some(parent))
+                if matches!(
+                    name,
+                    "plugins" | "load_macros_from_path" | "exclude_macros_from_path"
+                ) =>
+            {
+                Value::String(
+                    paths
+                        .split(',')
+                        .map(|path| parent.join(path.trim()).to_string_lossy().into_owned())
+                        .join(",")
+                        .into(),
+                )
+            }
+            (Value::String(relative), Some(parent))
+                if (name.ends_with("_path") || name.ends_with("_dir"))
+                    && Path::new(&*relative).is_relative() =>
+            {
+                Value::String(parent.join(&*relative).to_string_lossy().into())
+            }
+            (value, _) => value,
+        }
     }
 
     fn incorporate_vals(ctx: &mut AHashMap<String, Value>, values: Vec<(Vec<String>, Value)>) {
@@ -595,16 +1037,28 @@ impl FromStr for Value {
 }
 
 fn nested_combine(config_stack: Vec<AHashMap<String, Value>>) -> AHashMap<String, Value> {
-    let capacity = config_stack.len();
-    let mut result = AHashMap::with_capacity(capacity);
+    config_stack
+        .into_iter()
+        .fold(AHashMap::new(), combine_configs)
+}
 
-    for dict in config_stack {
-        for (key, value) in dict {
-            result.insert(key, value);
+/// Merges `b` onto `a`, section by section.
+fn combine_configs(
+    mut a: AHashMap<String, Value>,
+    b: AHashMap<String, Value>,
+) -> AHashMap<String, Value> {
+    for (key, value_b) in b {
+        match (a.get(&key), value_b) {
+            (Some(Value::Map(map_a)), Value::Map(map_b)) => {
+                let combined = combine_configs(map_a.clone(), map_b);
+                a.insert(key, Value::Map(combined));
+            }
+            (_, value) => {
+                a.insert(key, value);
+            }
         }
     }
-
-    result
+    a
 }
 
 impl<'a> From<&'a FluffConfig> for Parser<'a> {
@@ -618,3 +1072,117 @@ impl<'a> From<&'a FluffConfig> for Parser<'a> {
         Self::new(dialect, indentation_config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqruff_lib_core::dialects::init::DialectKind;
+
+    use super::{ConfigLoader, FluffConfig, Value};
+
+    #[test]
+    fn test_from_toml_source_pyproject() {
+        let source = r#"
+[tool.black]
+line-length = 88
+
+[tool.sqlfluff.core]
+dialect = "postgres"
+max_line_length = 100
+
+[tool.sqruff.core]
+dialect = "bigquery"
+rules = "all"
+exclude_rules = ["AM01", "AM02"]
+
+[tool.sqruff.rules]
+allow_scalar = false
+
+[tool.sqruff.rules.capitalisation.keywords]
+capitalisation_policy = "upper"
+
+[tool.sqruff.layout.type.comma]
+line_position = "leading"
+"#;
+        let config = FluffConfig::from_toml_source(source, None, true).unwrap();
+
+        assert_eq!(config.get_dialect().name, DialectKind::Bigquery);
+        assert_eq!(config.get("max_line_length", "core"), &Value::Int(100));
+        assert_eq!(
+            config.get("rule_denylist", "core"),
+            &Value::Array(vec![
+                Value::String("AM01".into()),
+                Value::String("AM02".into())
+            ])
+        );
+        assert_eq!(config.get("allow_scalar", "rules"), &Value::Bool(false));
+        assert_eq!(
+            config.raw["rules"]["capitalisation.keywords"]["capitalisation_policy"],
+            Value::String("upper".into())
+        );
+        assert_eq!(
+            config.raw["layout"]["type"]["comma"]["line_position"],
+            Value::String("leading".into())
+        );
+    }
+
+    #[test]
+    fn test_load_config_at_path_precedence() {
+        let configs = ConfigLoader
+            .load_config_at_path("test/fixtures/config/toml")
+            .unwrap();
+        let core = &configs["core"];
+
+        // `sqruff.toml` takes precedence over `.sqruff`, which takes
+        // precedence over `pyproject.toml`.
+        assert_eq!(core["dialect"], Value::String("bigquery".into()));
+        assert_eq!(core["exclude_rules"], Value::String("LT01".into()));
+        assert_eq!(core["max_line_length"], Value::Int(100));
+    }
+
+    #[test]
+    fn test_load_config_up_to_path_precedence() {
+        let root = "test/fixtures/config/hierarchical";
+        let configs = ConfigLoader
+            .load_config_up_to_path(root, None, false)
+            .unwrap();
+        assert_eq!(configs["core"]["rules"], Value::String("CP01".into()));
+        assert_eq!(configs["core"]["dialect"], Value::None);
+        assert_eq!(
+            configs["rules"]["capitalisation.keywords"]["capitalisation_policy"],
+            Value::String("upper".into())
+        );
+
+        // The nearer config takes precedence, and sections are merged key by
+        // key.
+        let configs = ConfigLoader
+            .load_config_up_to_path(format!("{root}/bigquery"), None, false)
+            .unwrap();
+        assert_eq!(configs["core"]["rules"], Value::String("CP01".into()));
+        assert_eq!(configs["core"]["dialect"], Value::String("bigquery".into()));
+        assert_eq!(
+            configs["rules"]["capitalisation.keywords"]["capitalisation_policy"],
+            Value::String("lower".into())
+        );
+    }
+
+    #[test]
+    fn test_load_config_at_path_invalid_toml() {
+        let error = ConfigLoader
+            .load_config_at_path("test/fixtures/config/invalid_toml")
+            .unwrap_err();
+        assert!(error
+            .value
+            .starts_with("Failed to parse test/fixtures/config/invalid_toml/sqruff.toml"));
+    }
+
+    #[test]
+    fn test_load_macros_from_path_relative_to_config() {
+        let configs = ConfigLoader
+            .load_config_at_path("test/fixtures/config/macros")
+            .unwrap();
+        assert_eq!(
+            configs["templater"]["jinja"]["load_macros_from_path"],
+            Value::String("test/fixtures/config/macros/macros,/opt/macros".into())
+        );
+    }
+}
@@ -1,5 +1,7 @@
+pub mod cache;
 pub mod common;
 pub mod core;
+pub mod encoding;
 pub mod linted_dir;
 pub mod linted_file;
 pub mod linting_result;
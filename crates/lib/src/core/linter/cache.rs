@@ -0,0 +1,390 @@
+use std::fmt::Write as _;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ahash::{AHashMap, AHashSet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqruff_lib_core::errors::{ErrorStructRule, SQLBaseError, Severity};
+
+use crate::core::config::{FluffConfig, Value};
+use crate::core::linter::linted_file::LintedFile;
+use crate::utils::complexity::StatementMetrics;
+
+/// The templaters which only take their input from the config and the file,
+/// so that their results can be cached.
+const CACHEABLE_TEMPLATERS: [&str; 3] = ["raw", "placeholder", "python"];
+
+/// How long an entry is kept without being used. Editing a file or its config
+/// leaves its old entry unused.
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// An on-disk cache of the results of linting files, keyed by a hash of the
+/// contents of each file along with everything else its results depend on:
+/// its effective config, the files the config loads rules from and the
+/// version of sqruff.
+///
+/// Changing a config file changes the effective config of the files below
+/// it, so their results are linted again rather than invalidated explicitly.
+/// Entries which haven't been used for [`MAX_AGE`] are pruned instead.
+pub struct LintCache {
+    dir: PathBuf,
+    /// The digests of the configs of the directories of linted files, `None`
+    /// for configs whose results can't be cached.
+    config_digests: Mutex<AHashMap<PathBuf, Option<String>>>,
+    /// Whether the cache directory has been created.
+    created: OnceLock<bool>,
+}
+
+impl LintCache {
+    pub const DEFAULT_DIR: &'static str = ".sqruff_cache";
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            config_digests: Mutex::default(),
+            created: OnceLock::new(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The key of the results of linting `bytes`, the contents of `fname`,
+    /// with `config`, the config of its directory. `None` if they can't be
+    /// cached, as the templater reads other files. Results with metrics are
+    /// cached apart from those without.
+    pub(crate) fn key(
+        &self,
+        fname: &str,
+        bytes: &[u8],
+        config: &FluffConfig,
+        include_parse_errors: bool,
+        metrics: bool,
+    ) -> Option<String> {
+        let dir = Path::new(fname).parent().unwrap_or(Path::new(""));
+        let config_digest = self
+            .config_digests
+            .lock()
+            .unwrap()
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| config_digest(config))
+            .clone()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(config_digest);
+        hasher.update([include_parse_errors as u8, metrics as u8]);
+        hasher.update(fname.len().to_le_bytes());
+        hasher.update(fname);
+        hasher.update(bytes);
+        Some(hex(&hasher.finalize()))
+    }
+
+    /// The cached results of linting `fname`, if there are any for `key`.
+    pub(crate) fn load(&self, key: &str, fname: &str) -> Option<LintedFile> {
+        let path = self.entry_path(key);
+        let contents = std::fs::read(&path).ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&contents).ok()?;
+
+        // Entries are kept for as long as they are used.
+        if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+            _ = file.set_modified(SystemTime::now());
+        }
+
+        Some(LintedFile {
+            path: fname.to_string(),
+            violations: entry
+                .violations
+                .into_iter()
+                .map(CachedViolation::into_error)
+                .collect::<Option<_>>()?,
+            metrics: entry.metrics,
+            ..LintedFile::default()
+        })
+    }
+
+    /// Cache the results of linting a file under `key`. The cache is only an
+    /// optimisation, so failing to write it is ignored.
+    pub(crate) fn store(&self, key: &str, linted_file: &LintedFile) {
+        if !*self.created.get_or_init(|| self.create_dir().is_ok()) {
+            return;
+        }
+
+        let entry = CacheEntry {
+            violations: linted_file
+                .violations
+                .iter()
+                .map(CachedViolation::from)
+                .collect(),
+            metrics: linted_file.metrics.clone(),
+        };
+        let Ok(contents) = serde_json::to_vec(&entry) else {
+            return;
+        };
+
+        // Entries are renamed into place, so that runs in parallel never read
+        // one which is half written.
+        let path = self.entry_path(key);
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        if std::fs::write(&temp_path, contents).is_err()
+            || std::fs::rename(&temp_path, path).is_err()
+        {
+            _ = std::fs::remove_file(temp_path);
+        }
+    }
+
+    /// Remove the entries which haven't been used for [`MAX_AGE`], such as
+    /// those of files which have been edited since.
+    pub(crate) fn prune(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let now = SystemTime::now();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.file_name().is_some_and(|name| name == ".gitignore") {
+                continue;
+            }
+
+            let is_stale = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| now.duration_since(modified).is_ok_and(|age| age > MAX_AGE));
+            if is_stale {
+                _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// Create the cache directory, which keeps itself out of version control.
+    fn create_dir(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let gitignore = self.dir.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(gitignore, "# Created by sqruff.\n*\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// A digest of everything lint results depend on besides the file: the
+/// version of sqruff, the config and the plugin and catalog files it loads.
+fn config_digest(config: &FluffConfig) -> Option<String> {
+    let templater = config.get("templater", "core").as_string().unwrap_or("raw");
+    if !CACHEABLE_TEMPLATERS.contains(&templater) {
+        return None;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    // Builds of the same version differ while it is being developed.
+    let built = std::env::current_exe()
+        .and_then(std::fs::metadata)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok());
+    hasher.update(built.unwrap_or_default().as_nanos().to_le_bytes());
+
+    let mut raw = String::new();
+    write_value(&mut raw, &Value::Map(config.raw.clone()));
+    hasher.update(raw);
+
+    let core = |key: &str| config.get(key, "core");
+    let paths = core("plugins")
+        .as_array()
+        .unwrap_or_default()
+        .into_iter()
+        .chain([core("catalog_path").clone()]);
+    for path in paths {
+        let Some(path) = path.as_string().map(str::trim).filter(|it| !it.is_empty()) else {
+            continue;
+        };
+        hasher.update(path);
+        match std::fs::read(path) {
+            Ok(contents) => hasher.update(Sha256::digest(contents)),
+            Err(_) => hasher.update([0]),
+        }
+    }
+
+    Some(hex(&hasher.finalize()))
+}
+
+/// Write a config value the same way whatever the order of its maps.
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Map(map) => {
+            out.push('{');
+            let mut keys = map.keys().collect::<Vec<_>>();
+            keys.sort();
+            for key in keys {
+                _ = write!(out, "{key:?}:");
+                write_value(out, &map[key]);
+                out.push(',');
+            }
+            out.push('}');
+        }
+        Value::Array(values) => {
+            out.push('[');
+            for value in values {
+                write_value(out, value);
+                out.push(',');
+            }
+            out.push(']');
+        }
+        value => _ = write!(out, "{value:?}"),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+/// The lint results of a file, as they are cached.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    violations: Vec<CachedViolation>,
+    metrics: Vec<StatementMetrics>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedViolation {
+    fatal: bool,
+    ignore: bool,
+    severity: String,
+    line_no: usize,
+    line_pos: usize,
+    description: String,
+    rule: Option<(String, String)>,
+    source_slice: Range<usize>,
+    fixable: bool,
+    templated: bool,
+}
+
+impl From<&SQLBaseError> for CachedViolation {
+    fn from(error: &SQLBaseError) -> Self {
+        Self {
+            fatal: error.fatal,
+            ignore: error.ignore,
+            severity: error.severity.as_ref().to_string(),
+            line_no: error.line_no,
+            line_pos: error.line_pos,
+            description: error.description.clone(),
+            rule: error
+                .rule
+                .as_ref()
+                .map(|rule| (rule.name.to_string(), rule.code.to_string())),
+            source_slice: error.source_slice.clone(),
+            fixable: error.fixable,
+            templated: error.templated,
+        }
+    }
+}
+
+impl CachedViolation {
+    fn into_error(self) -> Option<SQLBaseError> {
+        Some(SQLBaseError {
+            fatal: self.fatal,
+            ignore: self.ignore,
+            severity: Severity::from_str(&self.severity).ok()?,
+            line_no: self.line_no,
+            line_pos: self.line_pos,
+            description: self.description,
+            rule: self.rule.map(|(name, code)| ErrorStructRule {
+                name: intern(name),
+                code: intern(code),
+            }),
+            source_slice: self.source_slice,
+            fixable: self.fixable,
+            templated: self.templated,
+        })
+    }
+}
+
+/// The names and codes of rules are static, so those read from the cache are
+/// leaked, once each.
+fn intern(string: String) -> &'static str {
+    static INTERNED: Mutex<Option<AHashSet<&'static str>>> = Mutex::new(None);
+
+    let mut interned = INTERNED.lock().unwrap();
+    let interned = interned.get_or_insert_with(AHashSet::new);
+    match interned.get(string.as_str()) {
+        Some(string) => string,
+        None => {
+            let string: &'static str = string.leak();
+            interned.insert(string);
+            string
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::core::config::FluffConfig;
+    use crate::core::linter::core::Linter;
+
+    use super::LintCache;
+
+    fn lint(source: &str, cache_dir: &Path, path: &Path) -> Vec<String> {
+        let config = FluffConfig::from_source(source, None);
+        let mut linter =
+            Linter::new(config, None, None, false).with_cache(LintCache::new(cache_dir));
+        let mut result = linter.lint_paths(vec![path.to_path_buf()], false, &|_| false);
+        let file = std::mem::take(&mut result.paths[0].files[0]);
+        file.violations
+            .iter()
+            .map(|violation| format!("{}: {}", violation.rule_code(), violation.desc()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_cache() {
+        let dir = std::env::temp_dir().join(format!("sqruff-cache-{}", std::process::id()));
+        let cache_dir = dir.join(LintCache::DEFAULT_DIR);
+        let path = dir.join("query.sql");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "SELECT a  FROM t\n").unwrap();
+
+        let config = "[sqruff]\nrules = LT01\n";
+        let expected = ["LT01: Expected only single space before \"FROM\". Found \"  \"."];
+        assert_eq!(lint(config, &cache_dir, &path), expected);
+        assert!(cache_dir.join(".gitignore").is_file());
+
+        // Unchanged files are reported from the cache.
+        let entries = std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        let entry = std::fs::read_to_string(&entries[0]).unwrap();
+        std::fs::write(&entries[0], entry.replace("Expected", "Cached")).unwrap();
+        assert_eq!(
+            lint(config, &cache_dir, &path),
+            ["LT01: Cached only single space before \"FROM\". Found \"  \"."]
+        );
+
+        // Changing the config or the file lints it again.
+        assert_eq!(
+            lint("[sqruff]\nrules = LT01,CP01\n", &cache_dir, &path),
+            expected
+        );
+        std::fs::write(&path, "SELECT a FROM t\n").unwrap();
+        assert!(lint(config, &cache_dir, &path).is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
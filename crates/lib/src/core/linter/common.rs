@@ -1,7 +1,12 @@
+use std::sync::Arc;
+
 use sqruff_lib_core::errors::{SQLBaseError, SQLTemplaterError};
 use sqruff_lib_core::parser::segments::base::ErasedSegment;
 use sqruff_lib_core::templaters::base::TemplatedFile;
 
+use crate::core::config::FluffConfig;
+use crate::core::linter::encoding::FileEncoding;
+
 /// An object to store the result of a templated file/string.
 ///
 /// This is notable as it's the intermediate state between what happens
@@ -10,8 +15,14 @@ use sqruff_lib_core::templaters::base::TemplatedFile;
 pub struct RenderedFile {
     pub templated_file: TemplatedFile,
     pub templater_violations: Vec<SQLTemplaterError>,
+    /// Errors in reading the file or in its in-file config directives.
+    pub file_violations: Vec<SQLBaseError>,
     pub(crate) filename: String,
     pub source_str: String,
+    /// The config of the file, if it's not the linter's one.
+    pub config: Option<Arc<FluffConfig>>,
+    /// How the file was stored, to write fixes back the same way.
+    pub encoding: FileEncoding,
 }
 
 /// An object to store the result of parsing a string.
@@ -22,4 +33,8 @@ pub struct ParsedString {
     pub templated_file: TemplatedFile,
     pub filename: String,
     pub source_str: String,
+    /// The config of the file, if it's not the linter's one.
+    pub config: Option<Arc<FluffConfig>>,
+    /// How the file was stored, to write fixes back the same way.
+    pub encoding: FileEncoding,
 }
@@ -2,19 +2,22 @@ use std::borrow::Cow;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use super::linted_dir::LintedDir;
 use crate::cli::formatters::Formatter;
 use crate::core::config::FluffConfig;
+use crate::core::linter::cache::LintCache;
 use crate::core::linter::common::{ParsedString, RenderedFile};
+use crate::core::linter::encoding::FileEncoding;
 use crate::core::linter::linted_file::LintedFile;
 use crate::core::linter::linting_result::LintingResult;
-use crate::core::rules::base::{ErasedRule, LintPhase, RulePack};
+use crate::core::rules::base::{Erased, ErasedRule, LintPhase, RulePack};
 use crate::core::rules::noqa::IgnoreMask;
 use crate::rules::get_ruleset;
 use crate::templaters::raw::RawTemplater;
 use crate::templaters::{Templater, TEMPLATERS};
+use crate::utils::complexity::file_metrics;
 use ahash::{AHashMap, AHashSet};
 use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator as _, ParallelIterator as _};
@@ -22,7 +25,8 @@ use smol_str::{SmolStr, ToSmolStr};
 use sqruff_lib_core::dialects::base::Dialect;
 use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
 use sqruff_lib_core::errors::{
-    SQLBaseError, SQLFluffUserError, SQLLexError, SQLLintError, SQLParseError, SqlError,
+    ErrorStructRule, SQLBaseError, SQLFluffSkipFile, SQLFluffUserError, SQLLexError, SQLLintError,
+    SQLParseError, Severity, SqlError,
 };
 use sqruff_lib_core::helpers;
 use sqruff_lib_core::lint_fix::LintFix;
@@ -34,11 +38,24 @@ use sqruff_lib_core::parser::segments::fix::SourceFix;
 use sqruff_lib_core::templaters::base::TemplatedFile;
 use walkdir::WalkDir;
 
+/// The config of the files in a directory, with the rules it enables.
+#[derive(Clone)]
+struct DirectoryConfig {
+    config: Arc<FluffConfig>,
+    rule_pack: Arc<RulePack>,
+}
+
 pub struct Linter {
     config: FluffConfig,
     formatter: Option<Arc<dyn Formatter>>,
     templater: &'static dyn Templater,
-    rules: OnceLock<Vec<ErasedRule>>,
+    rule_pack: OnceLock<RulePack>,
+    /// The configs of the directories of linted files, shared by the files in
+    /// each directory, or the error loading them. `None` for the directories
+    /// which use the linter's own config.
+    directory_configs: Mutex<AHashMap<PathBuf, Result<Option<DirectoryConfig>, String>>>,
+    /// Where the results of linting files are cached, if anywhere.
+    cache: Option<LintCache>,
 
     /// include_parse_errors is a flag to indicate whether to include parse errors in the output
     include_parse_errors: bool,
@@ -59,11 +76,20 @@ impl Linter {
             config,
             formatter,
             templater,
-            rules: OnceLock::new(),
+            rule_pack: OnceLock::new(),
+            directory_configs: Mutex::default(),
+            cache: None,
             include_parse_errors,
         }
     }
 
+    /// This linter with the results of linting paths without fixing them
+    /// cached in `cache`, and reused while the files are unchanged.
+    pub fn with_cache(mut self, cache: LintCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     pub fn get_templater(config: &FluffConfig) -> &'static dyn Templater {
         let templater_name = config.get("templater", "core").as_string();
         match templater_name {
@@ -104,8 +130,6 @@ impl Linter {
 
         let mut violations: Vec<Box<dyn SqlError>> = vec![];
 
-        // Scan the raw file for config commands.
-        self.config.process_raw_file_for_config(sql);
         let rendered = self.render_string(sql, f_name.clone(), &self.config)?;
 
         for violation in &rendered.templater_violations {
@@ -168,26 +192,243 @@ impl Linter {
         expanded_paths
             .par_iter()
             .filter(|path| !ignorer(Path::new(path)))
-            .map(|path| {
-                let rendered = self.render_file(path.clone());
-                self.lint_rendered(rendered, fix)
-            })
+            .filter_map(|path| self.lint_path(path, fix))
             .for_each(|linted_file| {
                 let path = expanded_path_to_linted_dir[&linted_file.path];
                 result.paths[path].add(linted_file);
             });
 
+        if let Some(cache) = &self.cache {
+            cache.prune();
+        }
+
         result
     }
 
+    /// Lint the file at `path`, or reuse the results of an earlier run from
+    /// the cache if it is unchanged. `None` if the file is skipped.
+    fn lint_path(&self, path: &str, fix: bool) -> Option<LintedFile> {
+        let cached = self.cache.as_ref().filter(|_| !fix).and_then(|cache| {
+            let bytes = std::fs::read(path).ok()?;
+            let directory = self.directory_config(Path::new(path)).ok()?;
+            let config = directory.as_ref().map_or(&self.config, |it| &it.config);
+            let key = cache.key(
+                path,
+                &bytes,
+                config,
+                self.include_parse_errors,
+                self.wants_metrics(),
+            )?;
+            Some((cache, key))
+        });
+
+        if let Some(linted_file) = cached
+            .as_ref()
+            .and_then(|(cache, key)| cache.load(key, path))
+        {
+            if let Some(formatter) = &self.formatter {
+                formatter.dispatch_file_violations(&linted_file, false);
+            }
+            return Some(linted_file);
+        }
+
+        match self.render_file(path.to_string()) {
+            Ok(rendered) => {
+                let linted_file = self.lint_rendered(rendered, fix);
+                if let Some((cache, key)) = &cached {
+                    cache.store(key, &linted_file);
+                }
+                Some(linted_file)
+            }
+            Err(skip) => {
+                if let Some(formatter) = &self.formatter {
+                    formatter.dispatch_skipped_file(path, &skip.to_string());
+                }
+                None
+            }
+        }
+    }
+
     pub fn get_rulepack(&self) -> RulePack {
-        let rs = get_ruleset();
-        rs.get_rulepack(&self.config)
+        Self::rulepack_for(&self.config)
+    }
+
+    fn rulepack_for(config: &FluffConfig) -> RulePack {
+        let mut rs = get_ruleset();
+        // Invalid plugins and pattern rules are reported when the
+        // configuration is loaded.
+        for plugin in config.plugins().unwrap_or_default() {
+            rs.register(plugin.rule());
+        }
+        for rule in config.pattern_rules().unwrap_or_default() {
+            rs.register(rule.clone().erased());
+        }
+        rs.get_rulepack(config)
+    }
+
+    /// Render a file, unless it is over the `large_file_skip_byte_limit` or
+    /// `large_file_skip_char_limit` of its config and not exempt from them.
+    ///
+    /// A file which can't be read or decoded with the `encoding` setting is
+    /// rendered empty, with the error as its only violation.
+    pub fn render_file(&self, fname: String) -> Result<RenderedFile, SQLFluffSkipFile> {
+        let config = match self.directory_config(Path::new(&fname)) {
+            Ok(directory) => directory.map(|it| it.config),
+            Err(error) => {
+                return Ok(Self::unreadable_file(
+                    fname,
+                    format!("Unable to load the config of the file: {error}."),
+                    None,
+                ));
+            }
+        };
+        let file_config = config.as_deref().unwrap_or(&self.config);
+        let exempt = Self::is_large_file_exempt(file_config, &fname);
+
+        let bytes = match std::fs::read(&fname) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                return Ok(Self::unreadable_file(
+                    fname,
+                    format!("Unable to read the file: {error}."),
+                    config,
+                ));
+            }
+        };
+
+        let byte_limit = file_config
+            .get("large_file_skip_byte_limit", "core")
+            .as_int()
+            .unwrap_or(0);
+        if !exempt && byte_limit > 0 {
+            let size = bytes.len();
+            if size > byte_limit as usize {
+                return Err(SQLFluffSkipFile::new(format!(
+                    "Length of file '{fname}' is {size} bytes which is over the limit of \
+                     {byte_limit} bytes. Skipping to avoid parser lock. Users can increase this \
+                     limit in their config by setting the 'large_file_skip_byte_limit' value, \
+                     or disable by setting it to zero."
+                )));
+            }
+        }
+
+        let encoding_setting = file_config
+            .get("encoding", "core")
+            .as_string()
+            .unwrap_or("autodetect");
+        let (in_str, encoding) = match FileEncoding::decode(&bytes, encoding_setting) {
+            Ok(decoded) => decoded,
+            Err(description) => return Ok(Self::unreadable_file(fname, description, config)),
+        };
+
+        let char_limit = file_config
+            .get("large_file_skip_char_limit", "core")
+            .as_int()
+            .unwrap_or(0);
+        if !exempt && char_limit > 0 {
+            let length = in_str.chars().count();
+            if length > char_limit as usize {
+                return Err(SQLFluffSkipFile::new(format!(
+                    "Length of file '{fname}' is {length} characters which is over the limit \
+                     of {char_limit} characters. Skipping to avoid parser lock. Users can \
+                     increase this limit in their config by setting the \
+                     'large_file_skip_char_limit' value, or disable by setting it to zero."
+                )));
+            }
+        }
+
+        let mut rendered = self
+            .render_string_with_config(&in_str, fname, config)
+            .unwrap();
+        rendered.encoding = encoding;
+        Ok(rendered)
+    }
+
+    /// An empty rendering of a file which can't be read, so that the error
+    /// is reported for it and fixes leave it alone.
+    fn unreadable_file(
+        fname: String,
+        description: String,
+        config: Option<Arc<FluffConfig>>,
+    ) -> RenderedFile {
+        RenderedFile {
+            templated_file: TemplatedFile::from(""),
+            templater_violations: Vec::new(),
+            file_violations: vec![SQLBaseError {
+                fatal: true,
+                severity: Severity::Error,
+                line_no: 1,
+                line_pos: 1,
+                description,
+                ..SQLBaseError::default()
+            }],
+            filename: fname,
+            source_str: String::new(),
+            config,
+            encoding: FileEncoding::default(),
+        }
+    }
+
+    /// Whether the file matches one of the `large_file_skip_exempt` patterns,
+    /// which are relative to the working directory.
+    fn is_large_file_exempt(config: &FluffConfig, fname: &str) -> bool {
+        let patterns = config.large_file_skip_exempt().unwrap_or_default();
+        if patterns.is_empty() {
+            return false;
+        }
+
+        let path = Path::new(fname);
+        let working_dir = std::env::current_dir().unwrap_or_default();
+        let path = path.strip_prefix(&working_dir).unwrap_or(path);
+        let path = path.strip_prefix(".").unwrap_or(path);
+        patterns.iter().any(|pattern| pattern.matches_path(path))
     }
 
-    pub fn render_file(&self, fname: String) -> RenderedFile {
-        let in_str = std::fs::read_to_string(&fname).unwrap();
-        self.render_string(&in_str, fname, &self.config).unwrap()
+    /// The config of the files in the directory of `path`, if config files
+    /// below the working directory change the linter's config for them.
+    fn directory_config(&self, path: &Path) -> Result<Option<DirectoryConfig>, String> {
+        let dir = Self::config_dir(path);
+        if let Some(directory) = self.directory_configs.lock().unwrap().get(dir) {
+            return directory.clone();
+        }
+
+        let directory = self.config.for_directory(dir).map(|config| {
+            config.map(|config| DirectoryConfig {
+                rule_pack: Arc::new(Self::rulepack_for(&config)),
+                config: Arc::new(config),
+            })
+        });
+        self.directory_configs
+            .lock()
+            .unwrap()
+            .entry(dir.to_path_buf())
+            .or_insert(directory)
+            .clone()
+    }
+
+    /// The directory whose config applies to the file at `path`.
+    fn config_dir(path: &Path) -> &Path {
+        match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        }
+    }
+
+    /// The rules enabled by `config`, the config of the file `fname`. These
+    /// are the ones of its directory, unless its in-file config changes them.
+    fn file_rule_pack(&self, fname: &str, config: &Arc<FluffConfig>) -> Arc<RulePack> {
+        let rule_pack = match self
+            .directory_configs
+            .lock()
+            .unwrap()
+            .get(Self::config_dir(Path::new(fname)))
+        {
+            Some(Ok(Some(directory))) if Arc::ptr_eq(&directory.config, config) => {
+                Some(directory.rule_pack.clone())
+            }
+            _ => None,
+        };
+        rule_pack.unwrap_or_else(|| Arc::new(Self::rulepack_for(config)))
     }
 
     pub fn lint_rendered(&self, rendered: RenderedFile, fix: bool) -> LintedFile {
@@ -204,40 +445,117 @@ impl Linter {
     ) -> LintedFile {
         let mut violations = parsed_string.violations;
 
-        let (patches, ignore_mask, initial_linting_errors) =
-            parsed_string
-                .tree
-                .map_or((Vec::new(), None, Vec::new()), |erased_segment| {
-                    let (tree, ignore_mask, initial_linting_errors) = self.lint_fix_parsed(
-                        tables,
-                        erased_segment,
-                        &parsed_string.templated_file,
-                        fix,
-                    );
-                    let patches = tree.iter_patches(&parsed_string.templated_file);
-                    (patches, ignore_mask, initial_linting_errors)
-                });
+        let config = parsed_string.config.as_deref().unwrap_or(&self.config);
+        let file_rule_pack;
+        let rule_pack = match &parsed_string.config {
+            Some(config) => {
+                file_rule_pack = self.file_rule_pack(&parsed_string.filename, config);
+                &*file_rule_pack
+            }
+            None => self.rule_pack(),
+        };
+
+        let metrics = match &parsed_string.tree {
+            Some(tree) if self.wants_metrics() => file_metrics(tree),
+            _ => Vec::new(),
+        };
+
+        // Fixes can corrupt unparsable sections, so files with them are only
+        // fixed with `fix_even_unparsable`.
+        let unparsable = parsed_string.tree.as_ref().map_or(0, unparsable_count);
+        let fix_even_unparsable = config
+            .get("fix_even_unparsable", "core")
+            .as_bool()
+            .unwrap_or(false);
+        let mut fix_skipped = (fix && unparsable > 0 && !fix_even_unparsable).then(|| {
+            "Fixes not applied as the file has unparsable sections, which they could \
+             corrupt. Set fix_even_unparsable to apply them anyway."
+                .to_string()
+        });
+        let fix = fix && fix_skipped.is_none();
+
+        let (mut tree, mut ignore_mask, initial_linting_errors) = match parsed_string.tree {
+            Some(tree) => {
+                let (tree, ignore_mask, initial_linting_errors) = self.lint_fix_parsed(
+                    tables,
+                    tree,
+                    &parsed_string.templated_file,
+                    config,
+                    rule_pack,
+                    fix,
+                );
+                (Some(tree), ignore_mask, initial_linting_errors)
+            }
+            None => (None, None, Vec::new()),
+        };
         violations.extend(initial_linting_errors.into_iter().map_into());
 
         // Filter violations with ignore mask
-        let violations = violations
+        let mut violations: Vec<SQLBaseError> = violations
             .into_iter()
             .filter(|violation| {
                 ignore_mask
-                    .as_ref()
-                    .is_none_or(|ignore_mask| !ignore_mask.is_masked(violation))
+                    .as_mut()
+                    .is_none_or(|ignore_mask| !ignore_mask.mask(violation))
             })
             .collect();
 
-        // TODO Need to error out unused noqas
-        let linted_file = LintedFile {
+        let warn_unused_ignores = config
+            .get("warn_unused_ignores", "core")
+            .as_bool()
+            .unwrap_or(false);
+        if let (Some(tree), Some(ignore_mask)) = (&mut tree, &ignore_mask) {
+            if warn_unused_ignores {
+                let unused = ignore_mask.unused_violations(tables, tree);
+                if fix {
+                    let fixes = unused.iter().flat_map(|error| error.fixes.clone());
+                    let mut anchor_info = compute_anchor_edit_info(fixes);
+                    *tree = tree.apply_fixes(&mut anchor_info).0;
+                }
+                violations.extend(unused.into_iter().map_into());
+            }
+        }
+
+        let patches = tree.map_or_else(Vec::new, |tree| {
+            tree.iter_patches(&parsed_string.templated_file)
+        });
+
+        let mut linted_file = LintedFile {
             path: parsed_string.filename,
             patches,
             templated_file: parsed_string.templated_file,
             violations,
             ignore_mask,
+            metrics,
+            encoding: parsed_string.encoding,
+            fix_skipped: None,
         };
 
+        // The fixed file is parsed again, to hold back fixes which would stop
+        // it parsing or make more of it unparsable.
+        if fix && !linted_file.patches.is_empty() {
+            let fixed = linted_file.fix_string();
+            let fixed_unparsable = self
+                .render_string_with_config(
+                    &fixed,
+                    linted_file.path.clone(),
+                    parsed_string.config.clone(),
+                )
+                .ok()
+                .and_then(|rendered| self.parse_rendered(tables, rendered).tree)
+                .map(|tree| unparsable_count(&tree));
+
+            if fixed_unparsable.is_none_or(|fixed_unparsable| fixed_unparsable > unparsable) {
+                linted_file.patches.clear();
+                fix_skipped = Some(
+                    "Fixes not applied as the fixed file would have new parsing errors. \
+                     Please report this as a bug with a minimal query which demonstrates it."
+                        .to_string(),
+                );
+            }
+        }
+        linted_file.fix_skipped = fix_skipped;
+
         if let Some(formatter) = &self.formatter {
             formatter.dispatch_file_violations(&linted_file, false);
         }
@@ -250,6 +568,8 @@ impl Linter {
         tables: &Tables,
         mut tree: ErasedSegment,
         templated_file: &TemplatedFile,
+        config: &FluffConfig,
+        rule_pack: &RulePack,
         fix: bool,
     ) -> (ErasedSegment, Option<IgnoreMask>, Vec<SQLLintError>) {
         let mut tmp;
@@ -264,11 +584,22 @@ impl Linter {
 
         // If we are fixing then we want to loop up to the runaway_limit, otherwise just
         // once for linting.
-        let loop_limit = if fix { 10 } else { 1 };
+        let loop_limit = if fix {
+            config
+                .get("runaway_limit", "core")
+                .as_int()
+                .map_or(10, |limit| limit.max(1) as usize)
+        } else {
+            1
+        };
+        // The rules whose fixes would bring back an earlier version of the
+        // tree, and those which would still change it after the last loop,
+        // which are reported as not converging.
+        let mut oscillating_rules: Vec<&str> = Vec::new();
+        let mut runaway_rules: Vec<&str> = Vec::new();
         // Look for comment segments which might indicate lines to ignore.
-        let (ignore_mask, violations): (Option<IgnoreMask>, Vec<SQLBaseError>) = {
-            let disable_noqa = self
-                .config
+        let (mut ignore_mask, violations): (Option<IgnoreMask>, Vec<SQLBaseError>) = {
+            let disable_noqa = config
                 .get("disable_noqa", "core")
                 .as_bool()
                 .unwrap_or(false);
@@ -281,10 +612,17 @@ impl Linter {
         };
         initial_linting_errors.extend(violations.into_iter().map_into());
 
+        // Violations in templated code are either dropped or marked, to show
+        // them at their template tag.
+        let ignore_templated_areas = config
+            .get("ignore_templated_areas", "core")
+            .as_bool()
+            .unwrap_or(true);
+
         for phase in phases {
             let mut rules_this_phase = if phases.len() > 1 {
-                tmp = self
-                    .rules()
+                tmp = rule_pack
+                    .rules
                     .iter()
                     .filter(|rule| rule.lint_phase() == *phase)
                     .cloned()
@@ -292,19 +630,22 @@ impl Linter {
 
                 &tmp
             } else {
-                self.rules()
+                &rule_pack.rules
             };
 
-            for loop_ in 0..(if *phase == LintPhase::Main {
-                loop_limit
-            } else {
-                2
+            // When fixing, a loop past the limit only checks whether the
+            // fixes have converged.
+            for loop_ in 0..(match *phase {
+                LintPhase::Main if fix => loop_limit + 1,
+                LintPhase::Main => loop_limit,
+                _ => 2,
             }) {
                 let is_first_linter_pass = *phase == phases[0] && loop_ == 0;
+                let is_runaway_check = *phase == LintPhase::Main && loop_ == loop_limit;
                 let mut changed = false;
 
                 if is_first_linter_pass {
-                    rules_this_phase = self.rules();
+                    rules_this_phase = &rule_pack.rules;
                 }
 
                 let last_fixes = Vec::new();
@@ -319,17 +660,29 @@ impl Linter {
 
                     let linting_errors = rule.crawl(
                         tables,
-                        &self.config.dialect,
+                        &config.dialect,
                         templated_file,
                         tree.clone(),
-                        &self.config,
+                        config,
                     );
+                    let severity = rule_pack.severity(rule.code());
+                    // The directives masking the reported violations of the
+                    // first pass are used.
                     let linting_errors: Vec<SQLLintError> = linting_errors
                         .into_iter()
-                        .filter(|error| {
-                            !ignore_mask
-                                .clone()
-                                .is_some_and(|ignore_mask: IgnoreMask| ignore_mask.is_masked(error))
+                        .filter_map(|mut error| {
+                            error.templated =
+                                !templated_file.is_source_slice_literal(&error.source_slice);
+                            (!ignore_templated_areas || !error.templated).then_some(error)
+                        })
+                        .filter(|error| match &mut ignore_mask {
+                            Some(ignore_mask) if is_first_linter_pass => !ignore_mask.mask(error),
+                            Some(ignore_mask) => !ignore_mask.is_masked(error),
+                            None => true,
+                        })
+                        .map(|mut error| {
+                            error.severity = severity;
+                            error
                         })
                         .collect();
 
@@ -371,10 +724,21 @@ impl Linter {
                             (new_tree.raw().to_smolstr(), new_tree.get_source_fixes());
 
                         if previous_versions.insert(loop_check_tuple) {
+                            if is_runaway_check {
+                                if !runaway_rules.contains(&rule.code()) {
+                                    runaway_rules.push(rule.code());
+                                }
+                                continue;
+                            }
+
                             tree = new_tree;
                             changed = true;
                             continue;
                         }
+
+                        if !oscillating_rules.contains(&rule.code()) {
+                            oscillating_rules.push(rule.code());
+                        }
                     }
                 }
 
@@ -384,6 +748,27 @@ impl Linter {
             }
         }
 
+        if !runaway_rules.is_empty() {
+            initial_linting_errors.push(fix_loop_warning(
+                &tree,
+                format!(
+                    "Fixes didn't converge within the runaway_limit of {loop_limit} loops. \
+                     Rules with fixes left: {}.",
+                    runaway_rules.join(",")
+                ),
+            ));
+        }
+        if !oscillating_rules.is_empty() {
+            initial_linting_errors.push(fix_loop_warning(
+                &tree,
+                format!(
+                    "Fixes not applied as they would undo earlier fixes, which suggests the \
+                     rules conflict. Rules with fixes not applied: {}.",
+                    oscillating_rules.join(",")
+                ),
+            ));
+        }
+
         (tree, ignore_mask, initial_linting_errors)
     }
 
@@ -393,23 +778,48 @@ impl Linter {
         sql: &str,
         filename: String,
         config: &FluffConfig,
+    ) -> Result<RenderedFile, SQLFluffUserError> {
+        let config = (!std::ptr::eq(config, &self.config)).then(|| Arc::new(config.clone()));
+        self.render_string_with_config(sql, filename, config)
+    }
+
+    /// Template the file with `config`, or the linter's config if `None`.
+    fn render_string_with_config(
+        &self,
+        sql: &str,
+        filename: String,
+        config: Option<Arc<FluffConfig>>,
     ) -> Result<RenderedFile, SQLFluffUserError> {
         let sql = Self::normalise_newlines(sql);
 
+        // Scan the raw file for config commands.
+        let (file_config, file_violations) = config
+            .as_deref()
+            .unwrap_or(&self.config)
+            .process_raw_file_for_config(&sql);
+        let file_config = file_config.map(Arc::new).or(config);
+        let config = file_config.as_deref().unwrap_or(&self.config);
+
         if let Some(error) = config.verify_dialect_specified() {
             return Err(error);
         }
 
+        let templater = if config.get("templater", "core") == self.config.get("templater", "core") {
+            self.templater
+        } else {
+            Self::get_templater(config)
+        };
+
         let templater_violations = vec![];
-        match self
-            .templater
-            .process(sql.as_ref(), filename.as_str(), config, &self.formatter)
-        {
+        match templater.process(sql.as_ref(), filename.as_str(), config, &self.formatter) {
             Ok(templated_file) => Ok(RenderedFile {
                 templated_file,
                 templater_violations,
+                file_violations,
                 filename,
                 source_str: sql.to_string(),
+                config: file_config,
+                encoding: FileEncoding::default(),
             }),
             Err(err) => Err(SQLFluffUserError::new(format!(
                 "Failed to template file {} with error {:?}",
@@ -425,13 +835,11 @@ impl Linter {
             unimplemented!()
         }
 
-        let mut violations = Vec::new();
+        let config = rendered.config.as_deref().unwrap_or(&self.config);
+        let mut violations = rendered.file_violations;
         let tokens = if rendered.templated_file.is_templated() {
-            let (t, lvs) = Self::lex_templated_file(
-                tables,
-                rendered.templated_file.clone(),
-                &self.config.dialect,
-            );
+            let (t, lvs) =
+                Self::lex_templated_file(tables, rendered.templated_file.clone(), &config.dialect);
             if !lvs.is_empty() {
                 unimplemented!("violations.extend(lvs);")
             }
@@ -445,7 +853,7 @@ impl Linter {
             let (p, pvs) = Self::parse_tokens(
                 tables,
                 &token_list,
-                &self.config,
+                config,
                 Some(rendered.filename.to_string()),
                 self.include_parse_errors,
             );
@@ -461,6 +869,8 @@ impl Linter {
             templated_file: rendered.templated_file,
             filename: rendered.filename,
             source_str: rendered.source_str,
+            config: rendered.config,
+            encoding: rendered.encoding,
         }
     }
 
@@ -673,12 +1083,24 @@ impl Linter {
     }
 
     pub fn config_mut(&mut self) -> &mut FluffConfig {
-        self.rules = OnceLock::new();
+        self.rule_pack = OnceLock::new();
+        self.directory_configs = Mutex::default();
         &mut self.config
     }
 
     pub fn rules(&self) -> &[ErasedRule] {
-        self.rules.get_or_init(|| self.get_rulepack().rules)
+        &self.rule_pack().rules
+    }
+
+    fn rule_pack(&self) -> &RulePack {
+        self.rule_pack.get_or_init(|| self.get_rulepack())
+    }
+
+    /// Whether the formatter outputs the complexity metrics of each file.
+    fn wants_metrics(&self) -> bool {
+        self.formatter
+            .as_ref()
+            .is_some_and(|formatter| formatter.wants_metrics())
     }
 
     pub fn formatter(&self) -> Option<&Arc<dyn Formatter>> {
@@ -690,8 +1112,35 @@ impl Linter {
     }
 }
 
+/// The number of unparsable sections in a tree.
+fn unparsable_count(tree: &ErasedSegment) -> usize {
+    tree.recursive_crawl(
+        const { &SyntaxSet::single(SyntaxKind::Unparsable) },
+        true,
+        &SyntaxSet::EMPTY,
+        true,
+    )
+    .len()
+}
+
+/// The rule of the warnings of fixes which don't converge.
+const FIX_LOOP_RULE: ErrorStructRule = ErrorStructRule {
+    name: "fix_loop",
+    code: "LOOP",
+};
+
+/// A warning about the fixes of a file, reported at its start.
+fn fix_loop_warning(tree: &ErasedSegment, description: String) -> SQLLintError {
+    let mut warning = SQLLintError::new(&description, tree.clone(), false, Vec::new());
+    warning.rule = Some(FIX_LOOP_RULE);
+    warning.severity = Severity::Warning;
+    warning
+}
+
 #[cfg(test)]
 mod tests {
+    use sqruff_lib_core::dialects::init::DialectKind;
+    use sqruff_lib_core::errors::Severity;
     use sqruff_lib_core::parser::segments::base::Tables;
 
     use crate::core::config::FluffConfig;
@@ -817,11 +1266,408 @@ mod tests {
         assert!(parsed.violations.is_empty());
     }
 
+    #[test]
+    fn test_linter_inline_config() {
+        let linter = Linter::new(FluffConfig::default(), None, None, true);
+        let sql = "\
+-- sqlfluff:dialect:bigquery
+-- sqlfluff:rules:CP01
+-- sqruff:rules:capitalisation.keywords:capitalisation_policy:upper
+select `a` from t
+";
+        let linted = linter.lint_string(sql, None, false);
+        let violations = linted
+            .violations
+            .iter()
+            .map(|violation| (violation.rule_code(), violation.line_no, violation.line_pos))
+            .collect::<Vec<_>>();
+
+        assert_eq!(violations, [("CP01", 4, 1), ("CP01", 4, 12)]);
+        // The config of the linter is left as it is.
+        assert_eq!(linter.config().get_dialect().name, DialectKind::Ansi);
+    }
+
+    #[test]
+    fn test_linter_inline_config_errors() {
+        let linter = Linter::new(FluffConfig::default(), None, None, false);
+        let sql = "\
+-- sqlfluff:dialekt:bigquery
+-- sqlfluff:dialect
+-- sqlfluff:dialect:nope
+-- sqlfluff:rules:capitalisation.keywords:policy:upper
+-- sqlfluff:rules:capitalisation:severity:fatal
+-- sqlfluff:plugins:rule.wasm
+SELECT 1
+";
+        let linted = linter.lint_string(sql, None, false);
+        let violations = linted
+            .violations
+            .iter()
+            .map(|violation| (violation.line_no, violation.desc()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            violations,
+            [
+                (1, "Unknown in-file config 'dialekt'"),
+                (
+                    2,
+                    "Malformed in-file config 'dialect'. Expected '-- sqlfluff:<key>:<value>'"
+                ),
+                (3, "Unknown dialect 'nope'"),
+                (
+                    4,
+                    "Unknown in-file config 'rules:capitalisation.keywords:policy'"
+                ),
+                (
+                    5,
+                    "Invalid severity 'fatal', expected error, warning or info"
+                ),
+                (6, "'plugins' can't be set in a file"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_linter_directory_config() {
+        let mut linter = Linter::new(FluffConfig::default(), None, None, true);
+        let result = linter.lint_paths(
+            vec!["test/fixtures/config/hierarchical".into()],
+            false,
+            &|_| false,
+        );
+
+        let mut violations = result.paths[0]
+            .files
+            .iter()
+            .flat_map(|file| {
+                let path = file.path.replace('\\', "/");
+                file.violations.iter().map(move |violation| {
+                    (
+                        path.clone(),
+                        violation.rule_code(),
+                        violation.line_no,
+                        violation.line_pos,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        violations.sort();
+
+        // The nested config sets the dialect and overrides the capitalisation
+        // policy, keeping the rules selected above it.
+        let a = "test/fixtures/config/hierarchical/a.sql".to_string();
+        let b = "test/fixtures/config/hierarchical/bigquery/b.sql".to_string();
+        assert_eq!(
+            violations,
+            [
+                (a.clone(), "CP01", 1, 1),
+                (a, "CP01", 1, 10),
+                (b.clone(), "CP01", 1, 1),
+                (b, "CP01", 1, 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_linter_directory_config_invalid_severity() {
+        let mut linter = Linter::new(FluffConfig::default(), None, None, false);
+        let result = linter.lint_paths(
+            vec!["test/fixtures/config/invalid_severity".into()],
+            false,
+            &|_| false,
+        );
+
+        let file = &result.paths[0].files[0];
+        let violations = file
+            .violations
+            .iter()
+            .map(|violation| violation.desc())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            [
+                "Unable to load the config of the file: Invalid severity 'fatal' for 'lt01', \
+                 expected error, warning or info."
+            ]
+        );
+    }
+
     // test__linter__mask_templated_violations
     // test__linter__encoding
     // test_delayed_exception
     // test__attempt_to_change_templater_warning
 
+    #[test]
+    fn test_linter_ignore_templated_areas() {
+        let config = |ignore_templated_areas: bool| {
+            FluffConfig::from_source(
+                &format!(
+                    "[sqruff]\n\
+                     rules = AL02,CP01\n\
+                     templater = placeholder\n\
+                     ignore_templated_areas = {ignore_templated_areas}\n\
+                     [sqruff:templater:placeholder]\n\
+                     param_style = colon\n\
+                     cols = col_a a\n"
+                ),
+                None,
+            )
+        };
+        let sql = "SELECT\n    x, :cols\nfrom t\n";
+        let violations = |linter: &Linter| {
+            linter
+                .lint_string(sql, None, false)
+                .violations
+                .into_iter()
+                .map(|violation| {
+                    (
+                        violation.rule_code(),
+                        violation.line_no,
+                        violation.line_pos,
+                        violation.templated,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The templated alias is reported at its placeholder, and positions
+        // after it are those of the source.
+        let linter = Linter::new(config(false), None, None, true);
+        assert_eq!(
+            violations(&linter),
+            [("AL02", 2, 8, true), ("CP01", 3, 1, false)]
+        );
+
+        let linter = Linter::new(config(true), None, None, true);
+        assert_eq!(violations(&linter), [("CP01", 3, 1, false)]);
+
+        // The fix of the templated alias would edit the placeholder, so the
+        // violation is reported without it.
+        let linter = Linter::new(config(false), None, None, true);
+        let linted = linter.lint_string(sql, None, true);
+        assert!(linted
+            .violations
+            .iter()
+            .any(|violation| violation.rule_code() == "AL02"));
+        assert_eq!(linted.fix_string(), "SELECT\n    x, :cols\nFROM t\n");
+    }
+
+    #[test]
+    fn test_linter_large_file_skip() {
+        let path = "test/fixtures/linter/passing.sql".to_string();
+        let config = |extra: &str| FluffConfig::from_source(&format!("[sqruff]\n{extra}"), None);
+
+        // The file is 16 bytes long.
+        let mut linter = Linter::new(
+            config("large_file_skip_byte_limit = 10\n"),
+            None,
+            None,
+            true,
+        );
+        let skip = linter.render_file(path.clone()).unwrap_err();
+        assert!(skip
+            .to_string()
+            .contains("is 16 bytes which is over the limit of 10 bytes"));
+        // Skipped files are left out of the results.
+        let result = linter.lint_paths(vec![path.clone().into()], false, &|_| false);
+        assert_eq!(result.paths[0].files.len(), 0);
+
+        let linter = Linter::new(
+            config("large_file_skip_byte_limit = 0\nlarge_file_skip_char_limit = 10\n"),
+            None,
+            None,
+            true,
+        );
+        let skip = linter.render_file(path.clone()).unwrap_err();
+        assert!(skip
+            .to_string()
+            .contains("is 16 characters which is over the limit of 10 characters"));
+
+        let linter = Linter::new(
+            config(
+                "large_file_skip_byte_limit = 10\n\
+                 large_file_skip_exempt = other.sql,test/fixtures/linter/*.sql\n",
+            ),
+            None,
+            None,
+            true,
+        );
+        assert!(linter.render_file(path).is_ok());
+    }
+
+    #[test]
+    fn test_linter_encoding() {
+        let path = std::env::temp_dir().join(format!("sqruff-encoding-{}.sql", std::process::id()));
+        let fname = path.display().to_string();
+        let linter_for = |encoding: &str| {
+            let config = format!("[sqruff]\nrules = LT01\nencoding = {encoding}\n");
+            Linter::new(FluffConfig::from_source(&config, None), None, None, true)
+        };
+
+        // Fixes are written back as Latin-1 with the file's CRLF newlines.
+        std::fs::write(&path, b"SELECT 'caf\xE9'  AS a\r\nFROM t\r\n").unwrap();
+        let mut linter = linter_for("autodetect");
+        let mut result = linter.lint_paths(vec![path.clone()], true, &|_| false);
+        let file = std::mem::take(&mut result.paths[0].files[0]);
+        assert_eq!(file.violations.len(), 1);
+        assert!(file.persist_tree().unwrap());
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            b"SELECT 'caf\xE9' AS a\r\nFROM t\r\n"
+        );
+
+        // A file which can't be decoded is reported and left alone.
+        let mut linter = linter_for("utf-8");
+        let mut result = linter.lint_paths(vec![path.clone()], true, &|_| false);
+        let file = std::mem::take(&mut result.paths[0].files[0]);
+        assert_eq!(file.path, fname);
+        assert_eq!(
+            file.violations
+                .iter()
+                .map(|violation| violation.desc())
+                .collect::<Vec<_>>(),
+            ["Unable to decode the file as UTF-8."]
+        );
+        assert!(!file.persist_tree().unwrap());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_linter_fix_even_unparsable() {
+        let fix = |source: &str, sql: &str| {
+            let linter = Linter::new(FluffConfig::from_source(source, None), None, None, false);
+            let linted = linter.lint_string(sql, None, true);
+            (linted.fix_string(), linted.fix_skipped)
+        };
+
+        let (fixed, skipped) = fix("[sqruff]\nrules = LT01\n", "SELECT a  FROM t\nSELEC 1\n");
+        assert_eq!(fixed, "SELECT a  FROM t\nSELEC 1\n");
+        assert_eq!(
+            skipped.as_deref(),
+            Some(
+                "Fixes not applied as the file has unparsable sections, which they could \
+                 corrupt. Set fix_even_unparsable to apply them anyway."
+            )
+        );
+
+        let (fixed, skipped) = fix(
+            "[sqruff]\nrules = LT01\nfix_even_unparsable = True\n",
+            "SELECT a  FROM t\nSELEC 1\n",
+        );
+        assert_eq!(fixed, "SELECT a FROM t\nSELEC 1\n");
+        assert_eq!(skipped, None);
+
+        // Fixes which would make the file unparsable are held back.
+        let (fixed, skipped) = fix(
+            "[sqruff]\n\
+             rules = ORG01\n\
+             [sqruff:pattern_rules:ORG01]\n\
+             selector = keyword[raw=\"FROM\"]\n\
+             message = Open a subquery.\n\
+             replacement = FROM (\n",
+            "SELECT a FROM t\n",
+        );
+        assert_eq!(fixed, "SELECT a FROM t\n");
+        assert_eq!(
+            skipped.as_deref(),
+            Some(
+                "Fixes not applied as the fixed file would have new parsing errors. Please \
+                 report this as a bug with a minimal query which demonstrates it."
+            )
+        );
+    }
+
+    #[test]
+    fn test_linter_runaway_limit() {
+        let loop_warnings = |source: &str, sql: &str| {
+            let linter = Linter::new(FluffConfig::from_source(source, None), None, None, true);
+            let linted = linter.lint_string(sql, None, true);
+            let warnings = linted
+                .violations
+                .iter()
+                .filter(|violation| violation.rule_code() == "LOOP")
+                .map(|violation| (violation.severity, violation.desc().to_string()))
+                .collect::<Vec<_>>();
+            (warnings, linted.fix_string())
+        };
+
+        let (warnings, fixed) = loop_warnings("[sqruff]\nrules = LT01\n", "SELECT a  FROM t\n");
+        assert_eq!(warnings, []);
+        assert_eq!(fixed, "SELECT a FROM t\n");
+
+        // ORG02 turns `NVL` into `IFNULL`, which ORG01 turns into `COALESCE`
+        // in the next loop.
+        let chained = |runaway_limit: usize| {
+            loop_warnings(
+                &format!(
+                    "[sqruff]\n\
+                     rules = ORG01,ORG02\n\
+                     runaway_limit = {runaway_limit}\n\
+                     [sqruff:pattern_rules:ORG01]\n\
+                     selector = function_name_identifier[raw=\"IFNULL\"], word[raw=\"IFNULL\"]\n\
+                     message = Use COALESCE.\n\
+                     replacement = COALESCE\n\
+                     [sqruff:pattern_rules:ORG02]\n\
+                     selector = function_name_identifier[raw=\"NVL\"], word[raw=\"NVL\"]\n\
+                     message = Use IFNULL.\n\
+                     replacement = IFNULL\n"
+                ),
+                "SELECT NVL(a, b) FROM t\n",
+            )
+        };
+
+        // Fixes which converge in exactly `runaway_limit` loops.
+        let (warnings, fixed) = chained(2);
+        assert_eq!(warnings, []);
+        assert_eq!(fixed, "SELECT COALESCE(a, b) FROM t\n");
+
+        let (warnings, fixed) = chained(1);
+        assert_eq!(
+            warnings,
+            [(
+                Severity::Warning,
+                "Fixes didn't converge within the runaway_limit of 1 loops. Rules with fixes \
+                 left: ORG01."
+                    .to_string()
+            )]
+        );
+        assert_eq!(fixed, "SELECT IFNULL(a, b) FROM t\n");
+
+        // A single loop is enough for LT01.
+        let (warnings, fixed) = loop_warnings(
+            "[sqruff]\nrules = LT01\nrunaway_limit = 1\n",
+            "SELECT a  FROM t\n",
+        );
+        assert_eq!(warnings, []);
+        assert_eq!(fixed, "SELECT a FROM t\n");
+
+        let (warnings, fixed) = loop_warnings(
+            "[sqruff]\n\
+             rules = ORG01,ORG02\n\
+             [sqruff:pattern_rules:ORG01]\n\
+             selector = function_name_identifier[raw=\"NVL\"], word[raw=\"NVL\"]\n\
+             message = Use IFNULL.\n\
+             replacement = IFNULL\n\
+             [sqruff:pattern_rules:ORG02]\n\
+             selector = function_name_identifier[raw=\"IFNULL\"], word[raw=\"IFNULL\"]\n\
+             message = Use NVL.\n\
+             replacement = NVL\n",
+            "SELECT NVL(a, b) FROM t\n",
+        );
+        assert_eq!(
+            warnings,
+            [(
+                Severity::Warning,
+                "Fixes not applied as they would undo earlier fixes, which suggests the rules \
+                 conflict. Rules with fixes not applied: ORG02."
+                    .to_string()
+            )]
+        );
+        assert_eq!(fixed, "SELECT IFNULL(a, b) FROM t\n");
+    }
+
     #[test]
     #[ignore = "The implementation of Lexer::lex_templated_file is required"]
     fn test_advanced_api_methods() {
@@ -0,0 +1,208 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+/// How a file was stored: its encoding, whether it started with a byte order
+/// mark and its newlines, so that fixes are written back the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEncoding {
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+    pub newline: &'static str,
+}
+
+impl Default for FileEncoding {
+    fn default() -> Self {
+        Self {
+            encoding: UTF_8,
+            bom: false,
+            newline: "\n",
+        }
+    }
+}
+
+impl FileEncoding {
+    /// Decode the contents of a file with the `encoding` setting, which is
+    /// either `autodetect` or the label of an encoding, e.g. `utf-8`,
+    /// `utf-8-sig` or `latin-1`.
+    ///
+    /// Autodetection goes by the byte order mark, then looks for UTF-16
+    /// without one, then tries UTF-8 and falls back to Windows-1252, a
+    /// superset of Latin-1 which decodes any file.
+    pub fn decode(bytes: &[u8], setting: &str) -> Result<(String, FileEncoding), String> {
+        let setting = setting.trim().to_ascii_lowercase().replace('_', "-");
+
+        let (encoding, bom, contents) = if setting == "autodetect" {
+            if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+                (encoding, true, &bytes[bom_length..])
+            } else if let Some(encoding) = utf16_without_bom(bytes) {
+                (encoding, false, bytes)
+            } else if std::str::from_utf8(bytes).is_ok() {
+                (UTF_8, false, bytes)
+            } else {
+                (WINDOWS_1252, false, bytes)
+            }
+        } else {
+            let (label, with_bom) = match setting.strip_suffix("-sig") {
+                Some(label) => (label, true),
+                None => (setting.as_str(), false),
+            };
+            let encoding = Encoding::for_label(label.as_bytes())
+                .or_else(|| Encoding::for_label(label.replace('-', "").as_bytes()))
+                .ok_or_else(|| format!("Unknown encoding '{setting}'."))?;
+
+            // A byte order mark of the encoding is kept as it is.
+            match Encoding::for_bom(bytes) {
+                Some((bom_encoding, bom_length)) if bom_encoding == encoding => {
+                    (encoding, true, &bytes[bom_length..])
+                }
+                _ => (encoding, with_bom, bytes),
+            }
+        };
+
+        let text = encoding
+            .decode_without_bom_handling_and_without_replacement(contents)
+            .ok_or_else(|| format!("Unable to decode the file as {}.", encoding.name()))?
+            .into_owned();
+        let newline = if text.contains("\r\n") {
+            "\r\n"
+        } else if text.contains('\r') {
+            "\r"
+        } else {
+            "\n"
+        };
+
+        Ok((
+            text,
+            FileEncoding {
+                encoding,
+                bom,
+                newline,
+            },
+        ))
+    }
+
+    /// Encode the `\n` separated contents of a file, as it was read.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        let text = text.replace('\n', self.newline);
+
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            if self.bom {
+                bytes.extend(utf16_bytes(self.encoding, '\u{feff}' as u16));
+            }
+            for unit in text.encode_utf16() {
+                bytes.extend(utf16_bytes(self.encoding, unit));
+            }
+            return Ok(bytes);
+        }
+
+        if self.bom && self.encoding == UTF_8 {
+            bytes.extend_from_slice(b"\xEF\xBB\xBF");
+        }
+        let (encoded, _, unmappable) = self.encoding.encode(&text);
+        if unmappable {
+            return Err(format!(
+                "The fixed file has characters which can't be encoded as {}.",
+                self.encoding.name()
+            ));
+        }
+        bytes.extend_from_slice(&encoded);
+        Ok(bytes)
+    }
+}
+
+/// The UTF-16 byte order of files without a byte order mark, when every other
+/// byte is zero as for mostly ASCII text.
+fn utf16_without_bom(bytes: &[u8]) -> Option<&'static Encoding> {
+    if bytes.is_empty() || bytes.len() % 2 != 0 {
+        return None;
+    }
+
+    let zeros = |offset: usize| {
+        bytes
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|&&byte| byte == 0)
+    };
+    let (even, odd) = (zeros(0).count(), zeros(1).count());
+    let units = bytes.len() / 2;
+
+    if even == 0 && odd * 2 >= units {
+        Some(UTF_16LE)
+    } else if odd == 0 && even * 2 >= units {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+fn utf16_bytes(encoding: &'static Encoding, unit: u16) -> [u8; 2] {
+    if encoding == UTF_16LE {
+        unit.to_le_bytes()
+    } else {
+        unit.to_be_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+    use super::FileEncoding;
+
+    #[test]
+    fn test_encoding_autodetect() {
+        let cases: [(&[u8], _, bool); 5] = [
+            (b"SELECT 1\n", UTF_8, false),
+            (b"\xEF\xBB\xBFSELECT 1\n", UTF_8, true),
+            (b"\xFF\xFES\0E\0L\0\n\0", UTF_16LE, true),
+            (b"\0S\0E\0L\0\n", UTF_16BE, false),
+            (b"SELECT 'caf\xE9'\n", WINDOWS_1252, false),
+        ];
+
+        for (bytes, encoding, bom) in cases {
+            let (text, file_encoding) = FileEncoding::decode(bytes, "autodetect").unwrap();
+            assert_eq!(file_encoding.encoding, encoding);
+            assert_eq!(file_encoding.bom, bom);
+            assert!(!text.starts_with('\u{feff}'));
+            assert_eq!(file_encoding.encode(&text).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_encoding_explicit() {
+        let (text, encoding) = FileEncoding::decode(b"SELECT 'caf\xE9'\n", "latin-1").unwrap();
+        assert_eq!(text, "SELECT 'café'\n");
+        assert_eq!(encoding.encoding, WINDOWS_1252);
+
+        let (_, encoding) = FileEncoding::decode(b"SELECT 1\n", "utf-8-sig").unwrap();
+        assert!(encoding.bom);
+        assert_eq!(
+            encoding.encode("SELECT 1\n").unwrap(),
+            b"\xEF\xBB\xBFSELECT 1\n"
+        );
+
+        assert_eq!(
+            FileEncoding::decode(b"SELECT 'caf\xE9'\n", "utf-8").unwrap_err(),
+            "Unable to decode the file as UTF-8."
+        );
+        assert_eq!(
+            FileEncoding::decode(b"SELECT 1\n", "klingon").unwrap_err(),
+            "Unknown encoding 'klingon'."
+        );
+        let latin1 = FileEncoding::decode(b"SELECT 1\n", "latin1").unwrap().1;
+        assert!(latin1.encode("SELECT '\u{4e2d}'").is_err());
+    }
+
+    #[test]
+    fn test_encoding_newlines() {
+        let (text, encoding) =
+            FileEncoding::decode(b"SELECT 1\r\nFROM t\r\n", "autodetect").unwrap();
+        assert_eq!(encoding.newline, "\r\n");
+        assert_eq!(text, "SELECT 1\r\nFROM t\r\n");
+        assert_eq!(
+            encoding.encode("SELECT 1\nFROM t\n").unwrap(),
+            b"SELECT 1\r\nFROM t\r\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::ops::Range;
 
+use crate::core::linter::encoding::FileEncoding;
 use crate::core::rules::noqa::IgnoreMask;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
@@ -7,6 +8,8 @@ use sqruff_lib_core::errors::{SQLBaseError, SqlError};
 use sqruff_lib_core::parser::segments::fix::FixPatch;
 use sqruff_lib_core::templaters::base::{RawFileSlice, TemplatedFile};
 
+use crate::utils::complexity::StatementMetrics;
+
 #[derive(Debug, Default)]
 pub struct LintedFile {
     pub path: String,
@@ -14,6 +17,11 @@ pub struct LintedFile {
     pub templated_file: TemplatedFile,
     pub violations: Vec<SQLBaseError>,
     pub ignore_mask: Option<IgnoreMask>,
+    pub metrics: Vec<StatementMetrics>,
+    /// How the file was stored, to write fixes back the same way.
+    pub encoding: FileEncoding,
+    /// Why the fixes of the file were held back, if they could corrupt it.
+    pub fix_skipped: Option<String>,
 }
 
 impl LintedFile {
@@ -61,12 +69,12 @@ impl LintedFile {
         str_buff
     }
 
-    pub fn fix_string(self) -> String {
+    pub fn fix_string(&self) -> String {
         // Generate patches from the fixed tree. In the process we sort
         // and deduplicate them so that the resultant list is in the
         //  right order for the source file without any duplicates.
         let filtered_source_patches =
-            Self::generate_source_patches(self.patches, &self.templated_file);
+            Self::generate_source_patches(self.patches.clone(), &self.templated_file);
 
         // Any Template tags in the source file are off limits, unless we're explicitly
         // fixing the source file.
@@ -87,6 +95,20 @@ impl LintedFile {
         )
     }
 
+    /// Write the fixed file back to its path in its original encoding,
+    /// unless the fixes didn't change it. Returns whether it was written.
+    pub fn persist_tree(&self) -> Result<bool, String> {
+        let write_buff = self.fix_string();
+        if write_buff == self.templated_file.source_str {
+            return Ok(false);
+        }
+
+        let bytes = self.encoding.encode(&write_buff)?;
+        std::fs::write(&self.path, bytes)
+            .map_err(|error| format!("Unable to write {}: {error}", self.path))?;
+        Ok(true)
+    }
+
     fn generate_source_patches(
         patches: Vec<FixPatch>,
         _templated_file: &TemplatedFile,
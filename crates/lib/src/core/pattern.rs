@@ -0,0 +1,653 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ahash::{AHashMap, AHashSet};
+use itertools::Itertools;
+use regex::Regex;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::lexer::StringOrTemplate;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, SegmentBuilder};
+
+use crate::core::config::Value;
+use crate::core::plugin::Plugin;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+/// A rule defined in configuration which reports every segment matching a
+/// selector.
+///
+/// Pattern rules are set in `[sqruff:pattern_rules:<code>]` sections. See
+/// `docs/pattern_rules.md` for the selector syntax.
+#[derive(Debug, Clone)]
+pub struct PatternRule {
+    code: &'static str,
+    name: &'static str,
+    message: &'static str,
+    groups: &'static [RuleGroups],
+    selector: Selector,
+    replacement: Option<String>,
+}
+
+impl PatternRule {
+    /// Builds the rule with `code` from the options of its config section.
+    pub fn from_config(code: &str, config: &AHashMap<String, Value>) -> Result<Self, String> {
+        let code = code.to_uppercase();
+        if code.is_empty() || !code.chars().all(|it| it.is_ascii_alphanumeric()) {
+            return Err(format!("rule codes must be alphanumeric, got {code:?}"));
+        }
+
+        let option = |key: &str| config.get(key).and_then(text);
+
+        let source = option("selector").ok_or("missing option 'selector'")?;
+        let selector =
+            Selector::parse(&source).map_err(|error| format!("invalid selector: {error}"))?;
+        let message = option("message").ok_or("missing option 'message'")?;
+        let name = option("name").unwrap_or_else(|| format!("custom.{}", code.to_lowercase()));
+
+        let mut groups = vec![RuleGroups::All];
+        for group in option("groups").iter().flat_map(|it| it.split(',')) {
+            let group = group.trim();
+            if group.is_empty() {
+                continue;
+            }
+            let group =
+                RuleGroups::from_str(group).map_err(|_| format!("unknown rule group {group:?}"))?;
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+
+        // Rules are registered for the lifetime of the process, like the
+        // built-in rules.
+        Ok(PatternRule {
+            code: code.leak(),
+            name: name.leak(),
+            message: message.leak(),
+            groups: groups.leak(),
+            selector,
+            replacement: option("replacement"),
+        })
+    }
+}
+
+impl PartialEq for PatternRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+            && self.name == other.name
+            && self.message == other.message
+            && self.groups == other.groups
+            && self.selector.source == other.selector.source
+            && self.replacement == other.replacement
+    }
+}
+
+/// Loads the pattern rules of the `pattern_rules` config section, checking
+/// that their codes and names don't collide with the built-in rules or
+/// `plugins`.
+pub fn load_pattern_rules(
+    section: &AHashMap<String, Value>,
+    plugins: &[Arc<Plugin>],
+) -> Result<Vec<PatternRule>, String> {
+    let mut codes = AHashSet::new();
+    let mut names = AHashSet::new();
+    let plugins = plugins.iter().map(|plugin| plugin.rule());
+    for rule in crate::rules::rules().into_iter().chain(plugins) {
+        codes.insert(rule.code());
+        names.insert(rule.name());
+    }
+
+    let mut rules = Vec::with_capacity(section.len());
+    for (code, config) in section.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+        let rule = config
+            .as_map()
+            .ok_or_else(|| "expected a section".to_owned())
+            .and_then(|config| PatternRule::from_config(code, config))
+            .map_err(|error| format!("Invalid pattern rule {}: {error}", code.to_uppercase()))?;
+        if !codes.insert(rule.code) {
+            return Err(format!("Pattern rule {} already exists", rule.code));
+        }
+        if !names.insert(rule.name) {
+            return Err(format!("Pattern rule {} already exists", rule.name));
+        }
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
+
+impl Rule for PatternRule {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(self.clone().erased())
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.message
+    }
+
+    fn long_description(&self) -> &'static str {
+        ""
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        self.groups
+    }
+
+    fn code(&self) -> &'static str {
+        self.code
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        if !self
+            .selector
+            .matches(&context.segment, &context.parent_stack)
+        {
+            return Vec::new();
+        }
+
+        let fixes = match self.replacement.as_deref() {
+            None => Vec::new(),
+            Some("") => vec![LintFix::delete(context.segment.clone())],
+            Some(replacement) => vec![LintFix::replace(
+                context.segment.clone(),
+                tokens(context, replacement),
+                None,
+            )],
+        };
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            fixes,
+            Some(self.message.to_owned()),
+            None,
+        )]
+    }
+
+    fn is_fix_compatible(&self) -> bool {
+        self.replacement.is_some()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(self.selector.subjects()).into()
+    }
+}
+
+/// Lexes a replacement into new tokens.
+fn tokens(context: &RuleContext, sql: &str) -> Vec<ErasedSegment> {
+    let Ok((tokens, _)) = context
+        .dialect
+        .lexer()
+        .lex(context.tables, StringOrTemplate::String(sql))
+    else {
+        return Vec::new();
+    };
+
+    tokens
+        .iter()
+        .filter(|it| !it.is_type(SyntaxKind::EndOfFile))
+        .map(|it| SegmentBuilder::token(context.tables.next_id(), it.raw(), it.get_type()).finish())
+        .collect()
+}
+
+/// Config values are parsed eagerly, so options such as `message = 1` need
+/// converting back to text.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.to_string()),
+        Value::Int(value) => Some(value.to_string()),
+        Value::Float(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Map(_) | Value::Array(_) | Value::None => None,
+    }
+}
+
+/// A comma separated list of alternative selectors, matching segments which
+/// match any of them.
+#[derive(Debug, Clone)]
+pub struct Selector {
+    source: String,
+    alternatives: Vec<Complex>,
+}
+
+/// Compound selectors joined by combinators, such as `from_clause > join_clause`.
+#[derive(Debug, Clone)]
+struct Complex {
+    /// Whether the first compound must be a child of the `:has()` scope, as in
+    /// `:has(> keyword)`.
+    scoped: bool,
+    compounds: Vec<Compound>,
+    /// The combinator between each compound and the next.
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// A segment type, or `*` for any type, followed by predicates.
+#[derive(Debug, Clone)]
+struct Compound {
+    kind: Option<SyntaxKind>,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone)]
+enum Predicate {
+    /// `[raw="..."]`, comparing the raw text case-insensitively.
+    Raw(String),
+    /// `[raw~="..."]`, searching the raw text with a regular expression.
+    RawMatches(Regex),
+    /// `:has(...)`, matching segments with a matching descendant.
+    Has(Vec<Complex>),
+    /// `:not(...)`, matching segments which don't match.
+    Not(Vec<Complex>),
+}
+
+impl Selector {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = SelectorParser { source, offset: 0 };
+        let alternatives = parser.list(false)?;
+        parser.whitespace();
+        if parser.offset != source.len() {
+            return Err(parser.error("unexpected character"));
+        }
+
+        if alternatives
+            .iter()
+            .any(|complex| complex.compounds.last().unwrap().kind.is_none())
+        {
+            return Err("selectors must end with a segment type".into());
+        }
+
+        Ok(Selector {
+            source: source.to_owned(),
+            alternatives,
+        })
+    }
+
+    /// The types of the segments this selector can match.
+    fn subjects(&self) -> SyntaxSet {
+        self.alternatives
+            .iter()
+            .filter_map(|complex| complex.compounds.last().unwrap().kind)
+            .collect()
+    }
+
+    /// Whether `segment`, whose ancestors from the root are `ancestors`,
+    /// matches.
+    pub fn matches(&self, segment: &ErasedSegment, ancestors: &[ErasedSegment]) -> bool {
+        any_matches(&self.alternatives, segment, ancestors)
+    }
+}
+
+fn any_matches(
+    alternatives: &[Complex],
+    segment: &ErasedSegment,
+    ancestors: &[ErasedSegment],
+) -> bool {
+    alternatives
+        .iter()
+        .any(|complex| complex.matches(complex.compounds.len() - 1, segment, ancestors))
+}
+
+impl Complex {
+    /// Whether `segment` matches the compounds up to `index`, from right to
+    /// left.
+    fn matches(&self, index: usize, segment: &ErasedSegment, ancestors: &[ErasedSegment]) -> bool {
+        if !self.compounds[index].matches(segment, ancestors) {
+            return false;
+        }
+
+        if index == 0 {
+            return !self.scoped || ancestors.is_empty();
+        }
+
+        match self.combinators[index - 1] {
+            Combinator::Child => ancestors
+                .split_last()
+                .is_some_and(|(parent, rest)| self.matches(index - 1, parent, rest)),
+            Combinator::Descendant => (0..ancestors.len())
+                .rev()
+                .any(|it| self.matches(index - 1, &ancestors[it], &ancestors[..it])),
+        }
+    }
+}
+
+impl Compound {
+    fn matches(&self, segment: &ErasedSegment, ancestors: &[ErasedSegment]) -> bool {
+        if self.kind.is_some_and(|kind| !segment.is_type(kind)) {
+            return false;
+        }
+
+        self.predicates.iter().all(|predicate| match predicate {
+            Predicate::Raw(raw) => segment.raw().eq_ignore_ascii_case(raw),
+            Predicate::RawMatches(regex) => regex.is_match(segment.raw()),
+            Predicate::Has(alternatives) => has(alternatives, segment, &mut Vec::new()),
+            Predicate::Not(alternatives) => !any_matches(alternatives, segment, ancestors),
+        })
+    }
+}
+
+/// Whether a descendant of `segment` matches, where `ancestors` are the
+/// ancestors of the descendants below `segment`.
+fn has(
+    alternatives: &[Complex],
+    segment: &ErasedSegment,
+    ancestors: &mut Vec<ErasedSegment>,
+) -> bool {
+    segment.segments().iter().any(|child| {
+        if any_matches(alternatives, child, ancestors) {
+            return true;
+        }
+
+        ancestors.push(child.clone());
+        let found = has(alternatives, child, ancestors);
+        ancestors.pop();
+        found
+    })
+}
+
+struct SelectorParser<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl SelectorParser<'_> {
+    fn rest(&self) -> &str {
+        &self.source[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.offset += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{token}'")))
+        }
+    }
+
+    /// Skips whitespace, returning whether there was any.
+    fn whitespace(&mut self) -> bool {
+        let len = self.rest().len() - self.rest().trim_start().len();
+        self.offset += len;
+        len > 0
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{message} at offset {} of {:?}", self.offset, self.source)
+    }
+
+    /// Parses comma separated alternatives, which may start with `>` when
+    /// `relative`.
+    fn list(&mut self, relative: bool) -> Result<Vec<Complex>, String> {
+        let mut alternatives = vec![self.complex(relative)?];
+        loop {
+            self.whitespace();
+            if !self.eat(",") {
+                return Ok(alternatives);
+            }
+            alternatives.push(self.complex(relative)?);
+        }
+    }
+
+    fn complex(&mut self, relative: bool) -> Result<Complex, String> {
+        self.whitespace();
+        let scoped = relative && self.eat(">");
+        self.whitespace();
+
+        let mut complex = Complex {
+            scoped,
+            compounds: vec![self.compound()?],
+            combinators: Vec::new(),
+        };
+
+        loop {
+            let whitespace = self.whitespace();
+            let combinator = if self.eat(">") {
+                self.whitespace();
+                Combinator::Child
+            } else if whitespace && !matches!(self.peek(), None | Some(',' | ')')) {
+                Combinator::Descendant
+            } else {
+                return Ok(complex);
+            };
+
+            complex.combinators.push(combinator);
+            complex.compounds.push(self.compound()?);
+        }
+    }
+
+    fn compound(&mut self) -> Result<Compound, String> {
+        let any = self.eat("*");
+        let kind = if any {
+            None
+        } else {
+            let name: String = self
+                .rest()
+                .chars()
+                .take_while(|&it| it.is_ascii_alphanumeric() || it == '_')
+                .collect();
+            if name.is_empty() {
+                None
+            } else {
+                let kind = SyntaxKind::from_str(&name)
+                    .map_err(|_| self.error(&format!("unknown segment type {name:?}")))?;
+                self.offset += name.len();
+                Some(kind)
+            }
+        };
+
+        let mut compound = Compound {
+            kind,
+            predicates: Vec::new(),
+        };
+
+        loop {
+            let predicate = if self.eat("[") {
+                self.whitespace();
+                self.expect("raw")?;
+                self.whitespace();
+                let regex = self.eat("~");
+                self.expect("=")?;
+                self.whitespace();
+                let value = self.string()?;
+                self.whitespace();
+                self.expect("]")?;
+
+                if regex {
+                    Predicate::RawMatches(Regex::new(&value).map_err(|error| error.to_string())?)
+                } else {
+                    Predicate::Raw(value)
+                }
+            } else if self.eat(":has(") {
+                let alternatives = self.list(true)?;
+                self.whitespace();
+                self.expect(")")?;
+                Predicate::Has(alternatives)
+            } else if self.eat(":not(") {
+                let alternatives = self.list(false)?;
+                self.whitespace();
+                self.expect(")")?;
+                Predicate::Not(alternatives)
+            } else {
+                break;
+            };
+            compound.predicates.push(predicate);
+        }
+
+        if !any && compound.kind.is_none() && compound.predicates.is_empty() {
+            return Err(self.error("expected a segment type"));
+        }
+
+        Ok(compound)
+    }
+
+    /// Parses a quoted string, where a backslash escapes the quote and other
+    /// backslashes are kept for regular expressions.
+    fn string(&mut self) -> Result<String, String> {
+        let quote = match self.peek() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => return Err(self.error("expected a quoted string")),
+        };
+        self.offset += 1;
+
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((index, char)) = chars.next() {
+            match char {
+                '\\' => match chars.next() {
+                    Some((_, next)) if next == quote => value.push(next),
+                    Some((_, next)) => {
+                        value.push('\\');
+                        value.push(next);
+                    }
+                    None => break,
+                },
+                _ if char == quote => {
+                    self.offset += index + 1;
+                    return Ok(value);
+                }
+                _ => value.push(char),
+            }
+        }
+
+        Err(self.error("unterminated string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::FluffConfig;
+    use crate::core::linter::core::Linter;
+
+    fn lint(config: &str, sql: &str) -> Vec<(String, String)> {
+        let config = FluffConfig::from_source(config, None);
+        let mut linter = Linter::new(config, None, None, false);
+        let result = linter.lint_string_wrapped(sql, None, false);
+        result.paths[0].files[0]
+            .violations
+            .iter()
+            .map(|violation| {
+                (
+                    violation.rule_code().to_owned(),
+                    violation.desc().to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    fn fix(config: &str, sql: &str) -> String {
+        let config = FluffConfig::from_source(config, None);
+        let mut linter = Linter::new(config, None, None, false);
+        let mut result = linter.lint_string_wrapped(sql, None, true);
+        std::mem::take(&mut result.paths[0].files[0]).fix_string()
+    }
+
+    #[test]
+    fn has_with_raw_text() {
+        let config = r#"
+[sqruff]
+rules = ORG01
+
+[sqruff:pattern_rules:ORG01]
+selector = join_clause:has(> keyword[raw="cross"])
+message = CROSS JOIN is not allowed.
+"#;
+
+        assert_eq!(
+            lint(config, "SELECT a FROM t CROSS JOIN u\n"),
+            [("ORG01".to_owned(), "CROSS JOIN is not allowed.".to_owned())]
+        );
+        assert!(lint(config, "SELECT a FROM t INNER JOIN u ON t.a = u.a\n").is_empty());
+        assert!(lint(config, "SELECT a FROM t CROSS JOIN u -- noqa: ORG01\n").is_empty());
+    }
+
+    #[test]
+    fn descendant_and_not() {
+        let config = r#"
+[sqruff]
+rules = custom.org02
+
+[sqruff:pattern_rules:org02]
+selector = create_view_statement wildcard_expression:not(:has(dot))
+message = Views must list their columns.
+"#;
+
+        assert_eq!(lint(config, "CREATE VIEW v AS SELECT * FROM t\n").len(), 1);
+        assert!(lint(config, "CREATE VIEW v AS SELECT t.* FROM t\n").is_empty());
+        assert!(lint(config, "SELECT * FROM t\n").is_empty());
+    }
+
+    #[test]
+    fn replacement() {
+        let config = r#"
+[sqruff]
+rules = ORG03
+
+[sqruff:pattern_rules:ORG03]
+selector = function_name[raw~="(?i)^nvl$"]
+message = Use COALESCE instead of NVL.
+replacement = COALESCE
+"#;
+
+        assert_eq!(
+            fix(config, "SELECT nvl(a, 0) FROM t\n"),
+            "SELECT COALESCE(a, 0) FROM t\n"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (selector, error) in [
+            ("", "expected a segment type"),
+            ("not_a_type", "unknown segment type"),
+            ("keyword:has(", "expected a segment type"),
+            ("keyword[raw=cross]", "expected a quoted string"),
+            ("keyword[raw~=\"(\"]", "regex parse error"),
+            ("select_clause *", "must end with a segment type"),
+            ("> keyword", "expected a segment type"),
+        ] {
+            let actual = Selector::parse(selector).unwrap_err();
+            assert!(actual.contains(error), "{selector}: {actual}");
+        }
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let config = FluffConfig::from_source(
+            "[sqruff:pattern_rules:AL01]\nselector = keyword\nmessage = Taken.\n",
+            None,
+        );
+        assert_eq!(
+            config.pattern_rules().unwrap_err(),
+            "Pattern rule AL01 already exists"
+        );
+
+        let config =
+            FluffConfig::from_source("[sqruff:pattern_rules:ORG04]\nselector = keyword\n", None);
+        assert_eq!(
+            config.pattern_rules().unwrap_err(),
+            "Invalid pattern rule ORG04: missing option 'message'"
+        );
+    }
+}
@@ -0,0 +1,541 @@
+use std::fmt::{self, Debug};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ahash::{AHashMap, AHashSet};
+use serde::Deserialize;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, SegmentBuilder};
+use wasmi::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, RootOnlyCrawler, SegmentSeekerCrawler};
+
+/// The number of instructions a plugin may execute for each call, so that a
+/// plugin which doesn't terminate can't hang the linter.
+const FUEL: u64 = 1_000_000_000;
+
+/// The most memory a plugin may use, in bytes.
+const MEMORY: usize = 256 * 1024 * 1024;
+
+/// A rule defined by a WebAssembly module.
+///
+/// Modules run in a sandbox: they can't import any functions, so they have no
+/// access to the file system or network, and their memory and running time
+/// are limited. A module exports its linear memory as `memory` and these
+/// functions, where an `i64` result packs a pointer into the high 32 bits and
+/// a length into the low 32 bits:
+///
+/// * `alloc(len: i32) -> i32` allocates `len` bytes for the input of `eval`.
+/// * `metadata() -> i64` returns the JSON description of the rule.
+/// * `eval(ptr: i32, len: i32) -> i64` takes the JSON input for a segment and
+///   returns a JSON array of violations.
+///
+/// See `docs/plugins.md` for the JSON formats.
+pub struct Plugin {
+    path: String,
+    engine: Engine,
+    module: Module,
+    code: &'static str,
+    name: &'static str,
+    description: &'static str,
+    long_description: &'static str,
+    groups: &'static [RuleGroups],
+    crawl: SyntaxSet,
+    fix_compatible: bool,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    code: String,
+    name: String,
+    description: String,
+    #[serde(default)]
+    long_description: String,
+    #[serde(default)]
+    groups: Vec<String>,
+    #[serde(default)]
+    crawl: Vec<String>,
+    #[serde(default)]
+    fix_compatible: bool,
+}
+
+#[derive(Deserialize)]
+struct Violation {
+    #[serde(default)]
+    path: Vec<usize>,
+    description: Option<String>,
+    #[serde(default)]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Deserialize)]
+struct Fix {
+    #[serde(rename = "type")]
+    edit_type: EditType,
+    #[serde(default)]
+    path: Vec<usize>,
+    #[serde(default)]
+    edits: Vec<Edit>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EditType {
+    Replace,
+    CreateBefore,
+    CreateAfter,
+    Delete,
+}
+
+#[derive(Deserialize)]
+struct Edit {
+    #[serde(rename = "type")]
+    kind: String,
+    raw: String,
+}
+
+impl Plugin {
+    /// Loads the rule defined by the WebAssembly module at `path`.
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let wasm = std::fs::read(path)
+            .map_err(|error| format!("Unable to read plugin {path}: {error}"))?;
+        Self::from_bytes(path, &wasm).map_err(|error| format!("Invalid plugin {path}: {error}"))
+    }
+
+    /// Loads the rule defined by a WebAssembly module, where `path` is used to
+    /// name the plugin in errors.
+    pub fn from_bytes(path: &str, wasm: &[u8]) -> Result<Self, String> {
+        let mut config = wasmi::Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).map_err(|error| error.to_string())?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(format!(
+                "plugins can't import host functions, but it imports '{}::{}'",
+                import.module(),
+                import.name()
+            ));
+        }
+
+        let mut plugin = Plugin {
+            path: path.to_owned(),
+            engine,
+            module,
+            code: "",
+            name: "",
+            description: "",
+            long_description: "",
+            groups: &[],
+            crawl: SyntaxSet::EMPTY,
+            fix_compatible: false,
+        };
+
+        let metadata: Metadata = serde_json::from_slice(&plugin.call(None)?)
+            .map_err(|error| format!("invalid metadata: {error}"))?;
+
+        if metadata.code.is_empty() || !metadata.code.chars().all(|it| it.is_ascii_alphanumeric()) {
+            return Err(format!(
+                "rule codes must be alphanumeric, got {:?}",
+                metadata.code
+            ));
+        }
+
+        let mut groups = vec![RuleGroups::All];
+        for group in &metadata.groups {
+            let group =
+                RuleGroups::from_str(group).map_err(|_| format!("unknown rule group {group:?}"))?;
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+
+        plugin.crawl = metadata
+            .crawl
+            .iter()
+            .map(|kind| {
+                SyntaxKind::from_str(kind).map_err(|_| format!("unknown segment type {kind:?}"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Rules are registered for the lifetime of the process, like the
+        // built-in rules.
+        plugin.code = metadata.code.leak();
+        plugin.name = metadata.name.leak();
+        plugin.description = metadata.description.leak();
+        plugin.long_description = metadata.long_description.leak();
+        plugin.groups = groups.leak();
+        plugin.fix_compatible = metadata.fix_compatible;
+
+        Ok(plugin)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn rule(self: &Arc<Self>) -> ErasedRule {
+        PluginRule {
+            plugin: Arc::clone(self),
+            config: serde_json::Value::Object(<_>::default()),
+        }
+        .erased()
+    }
+
+    /// Calls `eval` with `input`, or `metadata` if there is no input, and
+    /// returns the output of the call.
+    fn call(&self, input: Option<&[u8]>) -> Result<Vec<u8>, String> {
+        let limits = StoreLimitsBuilder::new().memory_size(MEMORY).build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL).map_err(|error| error.to_string())?;
+
+        let instance = Linker::<StoreLimits>::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|it| it.start(&mut store))
+            .map_err(|error| error.to_string())?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or("missing export 'memory'")?;
+
+        let packed = match input {
+            Some(input) => {
+                let len = i32::try_from(input.len()).map_err(|error| error.to_string())?;
+                let ptr = instance
+                    .get_typed_func::<i32, i32>(&store, "alloc")
+                    .and_then(|alloc| alloc.call(&mut store, len))
+                    .map_err(|error| format!("alloc: {error}"))?;
+                memory
+                    .write(&mut store, ptr as usize, input)
+                    .map_err(|error| format!("alloc: {error}"))?;
+
+                instance
+                    .get_typed_func::<(i32, i32), i64>(&store, "eval")
+                    .and_then(|eval| eval.call(&mut store, (ptr, len)))
+                    .map_err(|error| format!("eval: {error}"))?
+            }
+            None => instance
+                .get_typed_func::<(), i64>(&store, "metadata")
+                .and_then(|metadata| metadata.call(&mut store, ()))
+                .map_err(|error| format!("metadata: {error}"))?,
+        };
+
+        let ptr = (packed as u64 >> 32) as usize;
+        let len = packed as u32 as usize;
+        let mut output = vec![0; len];
+        memory
+            .read(&store, ptr, &mut output)
+            .map_err(|error| format!("output out of bounds: {error}"))?;
+        Ok(output)
+    }
+}
+
+impl Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("path", &self.path)
+            .field("code", &self.code)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Plugin {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+/// Loads the plugins at `paths`, checking that their codes and names don't
+/// collide with each other or with the built-in rules.
+pub fn load_plugins(paths: &[String]) -> Result<Vec<Arc<Plugin>>, String> {
+    let mut codes = AHashSet::new();
+    let mut names = AHashSet::new();
+    for rule in crate::rules::rules() {
+        codes.insert(rule.code());
+        names.insert(rule.name());
+    }
+
+    let mut plugins = Vec::with_capacity(paths.len());
+    for path in paths {
+        let plugin = Plugin::from_path(path)?;
+        if !codes.insert(plugin.code) {
+            return Err(format!(
+                "Plugin {path} defines rule {}, which already exists",
+                plugin.code
+            ));
+        }
+        if !names.insert(plugin.name) {
+            return Err(format!(
+                "Plugin {path} defines rule {}, which already exists",
+                plugin.name
+            ));
+        }
+        plugins.push(Arc::new(plugin));
+    }
+
+    Ok(plugins)
+}
+
+#[derive(Debug, Clone)]
+struct PluginRule {
+    plugin: Arc<Plugin>,
+    config: serde_json::Value,
+}
+
+impl Rule for PluginRule {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(PluginRule {
+            plugin: Arc::clone(&self.plugin),
+            config: serde_json::Value::Object(
+                config
+                    .iter()
+                    .map(|(key, value)| (key.clone(), to_json(value)))
+                    .collect(),
+            ),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        self.plugin.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.plugin.description
+    }
+
+    fn long_description(&self) -> &'static str {
+        self.plugin.long_description
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        self.plugin.groups
+    }
+
+    fn code(&self) -> &'static str {
+        self.plugin.code
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let input = eval_input(
+            context.dialect.name.as_ref(),
+            &self.config,
+            &context.segment,
+        );
+
+        match self
+            .plugin
+            .call(Some(input.as_bytes()))
+            .and_then(|output| {
+                serde_json::from_slice::<Vec<Violation>>(&output)
+                    .map_err(|error| format!("invalid violations: {error}"))
+            })
+            .and_then(|violations| {
+                violations
+                    .into_iter()
+                    .map(|violation| lint_result(context, violation))
+                    .collect()
+            }) {
+            Ok(results) => results,
+            Err(error) => vec![LintResult::new(
+                context.segment.clone().into(),
+                Vec::new(),
+                Some(format!("Plugin {} failed: {error}", self.plugin.path)),
+                None,
+            )],
+        }
+    }
+
+    fn is_fix_compatible(&self) -> bool {
+        self.plugin.fix_compatible
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        if self.plugin.crawl.is_empty() {
+            RootOnlyCrawler.into()
+        } else {
+            SegmentSeekerCrawler::new(self.plugin.crawl.clone()).into()
+        }
+    }
+}
+
+/// The JSON passed to a plugin's `eval` for a segment.
+fn eval_input(dialect: &str, config: &serde_json::Value, segment: &ErasedSegment) -> String {
+    serde_json::json!({
+        "dialect": dialect,
+        "config": config,
+        "segment": segment.to_serialised(false, true),
+    })
+    .to_string()
+}
+
+fn lint_result(context: &RuleContext, violation: Violation) -> Result<LintResult, String> {
+    let anchor = resolve(&context.segment, &violation.path)?;
+
+    let mut fixes = Vec::with_capacity(violation.fixes.len());
+    for fix in violation.fixes {
+        let target = resolve(&context.segment, &fix.path)?;
+        let edits = fix
+            .edits
+            .into_iter()
+            .map(|edit| {
+                let kind = SyntaxKind::from_str(&edit.kind)
+                    .map_err(|_| format!("unknown segment type {:?}", edit.kind))?;
+                Ok(SegmentBuilder::token(context.tables.next_id(), &edit.raw, kind).finish())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        fixes.push(match fix.edit_type {
+            EditType::Replace => LintFix::replace(target, edits, None),
+            EditType::CreateBefore => LintFix::create_before(target, edits),
+            EditType::CreateAfter => LintFix::create_after(target, edits, None),
+            EditType::Delete => LintFix::delete(target),
+        });
+    }
+
+    Ok(LintResult::new(
+        anchor.into(),
+        fixes,
+        violation.description,
+        None,
+    ))
+}
+
+/// The segment at `path`, a list of child indices starting from `segment`.
+fn resolve(segment: &ErasedSegment, path: &[usize]) -> Result<ErasedSegment, String> {
+    let mut segment = segment.clone();
+    for &index in path {
+        segment = segment
+            .segments()
+            .get(index)
+            .cloned()
+            .ok_or_else(|| format!("path {path:?} doesn't refer to a segment"))?;
+    }
+    Ok(segment)
+}
+
+fn to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Int(value) => (*value).into(),
+        Value::Bool(value) => (*value).into(),
+        Value::Float(value) => (*value).into(),
+        Value::String(value) => value.as_ref().into(),
+        Value::Map(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), to_json(value)))
+                .collect(),
+        ),
+        Value::Array(values) => values.iter().map(to_json).collect(),
+        Value::None => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::FluffConfig;
+    use crate::core::linter::core::Linter;
+
+    /// A plugin which reports every `DISTINCT` and deletes it.
+    const NO_DISTINCT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 0) "{\"code\":\"ORG01\",\"name\":\"org.no_distinct\",\"description\":\"Avoid DISTINCT.\",\"groups\":[\"convention\"],\"crawl\":[\"select_clause_modifier\"],\"fix_compatible\":true}")
+          (data (i32.const 512) "[{\"description\":\"DISTINCT is not allowed.\",\"fixes\":[{\"type\":\"delete\"}]}]")
+          (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "metadata") (result i64) (i64.const 154))
+          (func (export "eval") (param i32 i32) (result i64)
+            (i64.or (i64.shl (i64.const 512) (i64.const 32)) (i64.const 72))))
+    "#;
+
+    fn plugin(source: &str) -> Result<Arc<Plugin>, String> {
+        let wasm = wat::parse_str(source).unwrap();
+        Plugin::from_bytes("test.wasm", &wasm).map(Arc::new)
+    }
+
+    #[test]
+    fn metadata() {
+        let plugin = plugin(NO_DISTINCT).unwrap();
+        let rule = plugin.rule();
+
+        assert_eq!(rule.code(), "ORG01");
+        assert_eq!(rule.name(), "org.no_distinct");
+        assert_eq!(rule.groups(), &[RuleGroups::All, RuleGroups::Convention]);
+        assert!(rule.is_fix_compatible());
+    }
+
+    #[test]
+    fn lint_and_fix() {
+        let path = std::env::temp_dir().join(format!("sqruff-plugin-{}.wasm", std::process::id()));
+        std::fs::write(&path, wat::parse_str(NO_DISTINCT).unwrap()).unwrap();
+        let config = FluffConfig::from_source(
+            &format!("[sqruff]\nrules = ORG01\nplugins = {}\n", path.display()),
+            None,
+        );
+        let mut linter = Linter::new(config, None, None, false);
+
+        let result = linter.lint_string_wrapped("SELECT DISTINCT a FROM t\n", None, false);
+        let violations = &result.paths[0].files[0].violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_code(), "ORG01");
+        assert_eq!(violations[0].desc(), "DISTINCT is not allowed.");
+
+        let result =
+            linter.lint_string_wrapped("SELECT DISTINCT a FROM t -- noqa: ORG01\n", None, false);
+        assert!(result.paths[0].files[0].violations.is_empty());
+
+        let mut result = linter.lint_string_wrapped("SELECT DISTINCT a FROM t\n", None, true);
+        let fixed = std::mem::take(&mut result.paths[0].files[0]).fix_string();
+        assert_eq!(fixed, "SELECT  a FROM t\n");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn eval_input_includes_config_and_tree() {
+        let tables = sqruff_lib_core::parser::segments::base::Tables::default();
+        let linter = Linter::new(FluffConfig::default(), None, None, false);
+        let parsed = linter.parse_string(&tables, "SELECT 1\n", None).unwrap();
+
+        let input = eval_input(
+            "ansi",
+            &serde_json::json!({"max": 3}),
+            &parsed.tree.unwrap(),
+        );
+        let input: serde_json::Value = serde_json::from_str(&input).unwrap();
+
+        assert_eq!(input["dialect"], "ansi");
+        assert_eq!(input["config"]["max"], 3);
+        assert_eq!(
+            input["segment"]["file"][0]["statement"][0]["select_statement"][0]["select_clause"][0]
+                ["keyword"],
+            "SELECT"
+        );
+    }
+
+    #[test]
+    fn imports_are_rejected() {
+        let error =
+            plugin(r#"(module (import "env" "read_file" (func)) (memory (export "memory") 1))"#)
+                .unwrap_err();
+
+        assert!(error.contains("can't import host functions"), "{error}");
+    }
+
+    #[test]
+    fn running_time_is_limited() {
+        let error = plugin(
+            r#"(module
+                 (memory (export "memory") 1)
+                 (func (export "metadata") (result i64) (loop (br 0)) (i64.const 0)))"#,
+        )
+        .unwrap_err();
+
+        assert!(error.contains("metadata"), "{error}");
+    }
+}
@@ -1,18 +1,17 @@
 use std::fmt::{self, Debug};
 use std::ops::Deref;
-
 use std::sync::Arc;
 
 use ahash::{AHashMap, AHashSet};
 use itertools::chain;
 use sqruff_lib_core::dialects::base::Dialect;
 use sqruff_lib_core::dialects::init::DialectKind;
-use sqruff_lib_core::errors::{ErrorStructRule, SQLLintError};
+use sqruff_lib_core::errors::{ErrorStructRule, SQLLintError, Severity};
 use sqruff_lib_core::helpers::{Config, IndexMap};
 use sqruff_lib_core::lint_fix::LintFix;
 use sqruff_lib_core::parser::segments::base::{ErasedSegment, Tables};
 use sqruff_lib_core::templaters::base::TemplatedFile;
-use strum_macros::AsRefStr;
+use strum_macros::{AsRefStr, EnumString};
 
 use super::context::RuleContext;
 use super::crawlers::{BaseCrawler, Crawler};
@@ -25,7 +24,7 @@ pub struct LintResult {
     source: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Copy, Hash, Eq, AsRefStr)]
+#[derive(Debug, Clone, PartialEq, Copy, Hash, Eq, AsRefStr, EnumString)]
 #[strum(serialize_all = "lowercase")]
 pub enum RuleGroups {
     All,
@@ -35,8 +34,13 @@ pub enum RuleGroups {
     Capitalisation,
     Convention,
     Layout,
+    Migration,
+    Performance,
     References,
+    Safety,
     Structure,
+    Types,
+    Window,
 }
 
 impl LintResult {
@@ -219,7 +223,7 @@ pub trait Rule: CloneRule + dyn_clone::DynClone + Debug + 'static + Send + Sync
 
     fn process_lint_result(
         &self,
-        res: LintResult,
+        mut res: LintResult,
         templated_file: &TemplatedFile,
         new_lerrs: &mut Vec<SQLLintError>,
     ) {
@@ -228,7 +232,21 @@ pub trait Rule: CloneRule + dyn_clone::DynClone + Debug + 'static + Send + Sync
             .iter()
             .any(|it| it.has_template_conflicts(templated_file))
         {
-            return;
+            // Fixes can't edit templated code. A violation in templated code
+            // is still reported without them, unless `ignore_templated_areas`
+            // drops it, while other ones are dropped.
+            let in_templated_code = res
+                .anchor
+                .as_ref()
+                .and_then(|anchor| anchor.get_position_marker())
+                .is_some_and(|marker| {
+                    !templated_file.is_source_slice_literal(&marker.source_slice)
+                });
+            if !in_templated_code {
+                return;
+            }
+
+            res.fixes.clear();
         }
 
         if let Some(lerr) = res.to_linting_error(self.erased(), res.fixes.clone()) {
@@ -285,6 +303,7 @@ pub struct RuleManifest {
 #[derive(Clone)]
 pub struct RulePack {
     pub(crate) rules: Vec<ErasedRule>,
+    severities: AHashMap<&'static str, Severity>,
     _reference_map: AHashMap<&'static str, AHashSet<&'static str>>,
 }
 
@@ -292,6 +311,11 @@ impl RulePack {
     pub fn rules(&self) -> Vec<ErasedRule> {
         self.rules.clone()
     }
+
+    /// The severity of the violations of the rule with `code`.
+    pub fn severity(&self, code: &str) -> Severity {
+        self.severities.get(code).copied().unwrap_or_default()
+    }
 }
 
 pub struct RuleSet {
@@ -299,6 +323,19 @@ pub struct RuleSet {
 }
 
 impl RuleSet {
+    pub(crate) fn register(&mut self, rule: ErasedRule) {
+        self.register.insert(
+            rule.code(),
+            RuleManifest {
+                code: rule.code(),
+                name: rule.name(),
+                description: rule.description(),
+                groups: rule.groups(),
+                rule_class: rule,
+            },
+        );
+    }
+
     fn rule_reference_map(&self) -> AHashMap<&'static str, AHashSet<&'static str>> {
         let valid_codes: AHashSet<_> = self.register.keys().copied().collect();
 
@@ -405,8 +442,39 @@ impl RuleSet {
             .filter(|&&r| expanded_allowlist.contains(r) && !expanded_denylist.contains(r))
             .collect();
 
+        let warnings: AHashSet<&'static str> = match config.get("warnings", "core").as_array() {
+            Some(array) => array
+                .iter()
+                .filter_map(|it| reference_map.get(it.as_string()?))
+                .flatten()
+                .copied()
+                .collect(),
+            None => AHashSet::new(),
+        };
+        // Invalid severities are reported when the config is loaded.
+        let severities = config.severities().unwrap_or_default();
+        let mut rule_severities = AHashMap::new();
+
         for code in keylist {
             let rule = self.register[code].rule_class.clone();
+
+            // The severity set for the rule takes precedence over the ones set
+            // for its groups, with `all` coming last.
+            let sections = chain(
+                [rule.config_ref()],
+                rule.groups()
+                    .iter()
+                    .filter(|&&group| group != RuleGroups::All)
+                    .chain([&RuleGroups::All])
+                    .map(AsRef::as_ref),
+            );
+            let severity = sections
+                .filter_map(|section| severities.get(section).copied())
+                .next()
+                .or_else(|| warnings.contains(code).then_some(Severity::Warning));
+            if let Some(severity) = severity {
+                rule_severities.insert(*code, severity);
+            }
             let rule_config_ref = rule.config_ref();
 
             let tmp = AHashMap::new();
@@ -422,6 +490,7 @@ impl RuleSet {
 
         RulePack {
             rules: instantiated_rules,
+            severities: rule_severities,
             _reference_map: reference_map,
         }
     }
@@ -1,8 +1,9 @@
-use ahash::HashSet;
+use ahash::{HashMap, HashSet};
 use itertools::Itertools;
 use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
-use sqruff_lib_core::errors::SQLBaseError;
-use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use sqruff_lib_core::errors::{ErrorStructRule, SQLBaseError, SQLLintError, Severity};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, Tables};
 
 /// The NoQA directive is a way to disable specific rules or all rules for a specific line or range of lines.
 /// Similar to flake8’s ignore, individual lines can be ignored by adding `-- noqa` to the end of the line.
@@ -60,13 +61,14 @@ impl NoQADirective {
                     return Err(SQLBaseError {
                         fatal: true,
                         ignore: false,
-                        warning: false,
+                        severity: Severity::Error,
                         line_no: 0,
                         line_pos: 0,
                         description: format!("Rule {} not found in rule set", rule),
                         rule: None,
                         source_slice: Default::default(),
                         fixable: false,
+                        templated: false,
                     });
                 }
             }
@@ -85,6 +87,24 @@ impl NoQADirective {
         }
     }
 
+    /// The source position of the comment of the directive.
+    fn position(&self) -> (usize, usize) {
+        match self {
+            NoQADirective::LineIgnoreAll(LineIgnoreAll {
+                line_no, line_pos, ..
+            })
+            | NoQADirective::LineIgnoreRules(LineIgnoreRules {
+                line_no, line_pos, ..
+            })
+            | NoQADirective::RangeIgnoreAll(RangeIgnoreAll {
+                line_no, line_pos, ..
+            })
+            | NoQADirective::RangeIgnoreRules(RangeIgnoreRules {
+                line_no, line_pos, ..
+            }) => (*line_no, *line_pos),
+        }
+    }
+
     /// Extract ignore mask entries from a comment string, returning a NoQADirective if found. It
     /// does not validate the directive rules, only parses it.
     fn parse_from_comment(
@@ -128,7 +148,7 @@ impl NoQADirective {
                                 Err(SQLBaseError {
                                     fatal: true,
                                     ignore: false,
-                                    warning: false,
+                                    severity: Severity::Error,
                                     line_no,
                                     line_pos,
                                     description: "Malformed 'noqa' section. Expected 'noqa: <rule>[,...] | all'"
@@ -136,6 +156,7 @@ impl NoQADirective {
                                     rule: None,
                                     source_slice: Default::default(),
                                     fixable: false,
+                                    templated: false,
                                 })
                             } else {
                                 Ok(Some(NoQADirective::RangeIgnoreRules(RangeIgnoreRules {
@@ -166,7 +187,7 @@ impl NoQADirective {
                                 Err(SQLBaseError {
                                     fatal: true,
                                     ignore: false,
-                                    warning: false,
+                                    severity: Severity::Error,
                                     line_no,
                                     line_pos,
                                     description:
@@ -175,6 +196,7 @@ impl NoQADirective {
                                     rule: None,
                                     source_slice: Default::default(),
                                     fixable: false,
+                                    templated: false,
                                 })
                             } else {
                                 Ok(Some(NoQADirective::RangeIgnoreRules(RangeIgnoreRules {
@@ -187,12 +209,15 @@ impl NoQADirective {
                             }
                         }
                     } else if !comment.is_empty() {
-                        let rules = comment.split(",").map_into().collect::<HashSet<String>>();
+                        let rules = comment
+                            .split(",")
+                            .map(|rule| rule.trim().to_string())
+                            .collect::<HashSet<String>>();
                         if rules.is_empty() {
                             Err(SQLBaseError {
                                 fatal: true,
                                 ignore: false,
-                                warning: false,
+                                severity: Severity::Error,
                                 line_no,
                                 line_pos,
                                 description:
@@ -201,11 +226,12 @@ impl NoQADirective {
                                 rule: None,
                                 source_slice: Default::default(),
                                 fixable: false,
+                                templated: false,
                             })
                         } else {
                             return Ok(Some(NoQADirective::LineIgnoreRules(LineIgnoreRules {
                                 line_no,
-                                line_pos: 0,
+                                line_pos,
                                 raw_string: original_comment.into(),
                                 rules,
                             })));
@@ -214,7 +240,7 @@ impl NoQADirective {
                         Err(SQLBaseError {
                             fatal: true,
                             ignore: false,
-                            warning: false,
+                            severity: Severity::Error,
                             line_no,
                             line_pos,
                             description:
@@ -223,13 +249,14 @@ impl NoQADirective {
                             rule: None,
                             source_slice: Default::default(),
                             fixable: false,
+                            templated: false,
                         })
                     }
                 } else {
                     Err(SQLBaseError {
                         fatal: true,
                         ignore: false,
-                        warning: false,
+                        severity: Severity::Error,
                         line_no,
                         line_pos,
                         description:
@@ -238,6 +265,7 @@ impl NoQADirective {
                         rule: None,
                         source_slice: Default::default(),
                         fixable: false,
+                        templated: false,
                     })
                 }
             } else {
@@ -288,13 +316,34 @@ struct LineIgnoreRules {
     rules: HashSet<String>,
 }
 
+/// The violations a directive masked.
+#[derive(Debug, Clone, Default)]
+struct DirectiveUsage {
+    masked: bool,
+    rules: HashSet<String>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct IgnoreMask {
     ignore_list: Vec<NoQADirective>,
+    /// The usage of the directives of `ignore_list`, recorded by `mask`.
+    usage: Vec<DirectiveUsage>,
 }
 
 const NOQA_PREFIX: &str = "noqa";
 
+const COMMENT_TYPES: SyntaxSet = SyntaxSet::new(&[
+    SyntaxKind::Comment,
+    SyntaxKind::InlineComment,
+    SyntaxKind::BlockComment,
+]);
+
+/// The rule of the violations of unused directives.
+const UNUSED_NOQA_RULE: ErrorStructRule = ErrorStructRule {
+    name: "noqa",
+    code: "NOQA",
+};
+
 impl IgnoreMask {
     /// Extract ignore mask entries from a comment segment
     fn extract_ignore_from_comment(
@@ -317,13 +366,14 @@ impl IgnoreMask {
             .ok_or(SQLBaseError {
                 fatal: true,
                 ignore: false,
-                warning: false,
+                severity: Severity::Error,
                 line_no: 0,
                 line_pos: 0,
                 description: "Could not get position marker".to_string(),
                 rule: None,
                 source_slice: Default::default(),
                 fixable: false,
+                templated: false,
             })?
             .source_position();
         NoQADirective::parse_from_comment(comment_content, line_no, line_pos)
@@ -335,18 +385,7 @@ impl IgnoreMask {
     pub fn from_tree(tree: &ErasedSegment) -> (IgnoreMask, Vec<SQLBaseError>) {
         let mut ignore_list: Vec<NoQADirective> = vec![];
         let mut violations: Vec<SQLBaseError> = vec![];
-        for comment in tree.recursive_crawl(
-            const {
-                &SyntaxSet::new(&[
-                    SyntaxKind::Comment,
-                    SyntaxKind::InlineComment,
-                    SyntaxKind::BlockComment,
-                ])
-            },
-            false,
-            &SyntaxSet::new(&[]),
-            false,
-        ) {
+        for comment in tree.recursive_crawl(&COMMENT_TYPES, false, &SyntaxSet::new(&[]), false) {
             let ignore_entry = IgnoreMask::extract_ignore_from_comment(comment);
             if let Err(err) = ignore_entry {
                 violations.push(err);
@@ -354,120 +393,342 @@ impl IgnoreMask {
                 ignore_list.push(ignore_entry);
             }
         }
-        (IgnoreMask { ignore_list }, violations)
+        (
+            IgnoreMask {
+                ignore_list,
+                usage: Vec::new(),
+            },
+            violations,
+        )
     }
 
     /// is_masked returns true if the IgnoreMask masks the violation
-    /// TODO - The parsing should also return warnings for rules that aren't used
     pub fn is_masked(&self, violation: &SQLBaseError) -> bool {
-        fn is_masked_by_line_rules(ignore_mask: &IgnoreMask, violation: &SQLBaseError) -> bool {
-            for ignore in &ignore_mask.ignore_list {
-                match ignore {
-                    NoQADirective::LineIgnoreAll(LineIgnoreAll { line_no, .. }) => {
-                        if violation.line_no == *line_no {
-                            return true;
-                        }
-                    }
-                    NoQADirective::LineIgnoreRules(LineIgnoreRules { line_no, rules, .. }) => {
-                        if violation.line_no == *line_no {
-                            if let Some(rule) = &violation.rule {
-                                if rules.contains(rule.code) {
-                                    return true;
-                                }
-                            }
-                        }
-                    }
-                    _ => {}
+        !self.masked_by(violation).is_empty()
+    }
+
+    /// Like `is_masked`, also recording the directives which mask the
+    /// violation so that the unused ones can be reported.
+    pub fn mask(&mut self, violation: &SQLBaseError) -> bool {
+        let masked_by = self.masked_by(violation);
+        self.usage
+            .resize_with(self.ignore_list.len(), Default::default);
+        for &idx in &masked_by {
+            let usage = &mut self.usage[idx];
+            usage.masked = true;
+            if let Some(rule) = &violation.rule {
+                usage.rules.insert(rule.code.to_string());
+            }
+        }
+        !masked_by.is_empty()
+    }
+
+    /// The indices of the directives which mask the violation.
+    fn masked_by(&self, violation: &SQLBaseError) -> Vec<usize> {
+        let rule_code = violation.rule.as_ref().map(|rule| rule.code);
+        let mut masked_by = Vec::new();
+
+        for (idx, ignore) in self.ignore_list.iter().enumerate() {
+            match ignore {
+                NoQADirective::LineIgnoreAll(LineIgnoreAll { line_no, .. })
+                    if violation.line_no == *line_no =>
+                {
+                    masked_by.push(idx);
+                }
+                NoQADirective::LineIgnoreRules(LineIgnoreRules { line_no, rules, .. })
+                    if violation.line_no == *line_no
+                        && rule_code.is_some_and(|code| rules.contains(code)) =>
+                {
+                    masked_by.push(idx);
                 }
+                _ => {}
             }
-            false
         }
 
-        /// is_masked_by_range returns true if the violation is masked by the RangeIgnoreRules and
-        /// RangeIgnoreAll components in the ignore mask
-        fn is_masked_by_range_rules(ignore_mask: &IgnoreMask, violation: &SQLBaseError) -> bool {
-            // Collect RangeIgnore directives
-            let mut directives = Vec::new();
-
-            for ignore in &ignore_mask.ignore_list {
-                match ignore {
-                    NoQADirective::RangeIgnoreAll(RangeIgnoreAll {
-                        line_no, line_pos, ..
-                    }) => {
-                        directives.push((line_no, line_pos, ignore));
-                    }
-                    NoQADirective::RangeIgnoreRules(RangeIgnoreRules {
-                        line_no, line_pos, ..
-                    }) => {
-                        directives.push((line_no, line_pos, ignore));
+        // The range directives before the violation leave the rules
+        // disabled by the last directive which disabled them.
+        let mut all_rules_disabled_by = None;
+        let mut rules_disabled_by = HashMap::<&str, usize>::default();
+        for (idx, ignore) in self.range_directives() {
+            if ignore.position() > (violation.line_no, violation.line_pos) {
+                break;
+            }
+
+            match ignore {
+                NoQADirective::RangeIgnoreAll(RangeIgnoreAll { action, .. }) => {
+                    all_rules_disabled_by = (*action == IgnoreAction::Disable).then_some(idx);
+                }
+                NoQADirective::RangeIgnoreRules(RangeIgnoreRules { action, rules, .. }) => {
+                    for rule in rules {
+                        match action {
+                            IgnoreAction::Disable => {
+                                rules_disabled_by.insert(rule, idx);
+                            }
+                            IgnoreAction::Enable => {
+                                rules_disabled_by.remove(rule.as_str());
+                            }
+                        }
                     }
-                    _ => {}
                 }
+                _ => {}
             }
+        }
 
-            // Sort directives by line_no, line_pos
-            directives.sort_by(|(line_no1, line_pos1, _), (line_no2, line_pos2, _)| {
-                line_no1.cmp(line_no2).then(line_pos1.cmp(line_pos2))
-            });
+        if let Some(idx) = all_rules_disabled_by {
+            masked_by.push(idx);
+        } else if let Some(&idx) = rule_code.and_then(|code| rules_disabled_by.get(code)) {
+            masked_by.push(idx);
+        }
 
-            // Initialize state
-            let mut all_rules_disabled = false;
-            let mut disabled_rules = <HashSet<String>>::default();
+        masked_by
+    }
 
-            // For each directive
-            for (line_no, line_pos, ignore) in directives {
-                // Check if the directive is before the violation
-                if *line_no > violation.line_no {
-                    break;
-                }
-                if *line_no == violation.line_no && *line_pos > violation.line_pos {
-                    break;
-                }
+    /// The `disable=` and `enable=` directives with their indices, in the
+    /// order of the file.
+    fn range_directives(&self) -> Vec<(usize, &NoQADirective)> {
+        self.ignore_list
+            .iter()
+            .enumerate()
+            .filter(|(_, ignore)| {
+                matches!(
+                    ignore,
+                    NoQADirective::RangeIgnoreAll(_) | NoQADirective::RangeIgnoreRules(_)
+                )
+            })
+            .sorted_by_key(|(_, ignore)| ignore.position())
+            .collect()
+    }
 
-                // Process the directive
-                match ignore {
-                    NoQADirective::RangeIgnoreAll(RangeIgnoreAll { action, .. }) => match action {
-                        IgnoreAction::Disable => {
-                            all_rules_disabled = true;
-                        }
-                        IgnoreAction::Enable => {
-                            all_rules_disabled = false;
+    /// The violations of the directives, or of the rules of directives, which
+    /// didn't mask any violation passed to `mask`, with fixes removing them
+    /// from the comments in `tree`.
+    ///
+    /// An `enable=` directive is unused when it doesn't end a range which
+    /// masked a violation, so the two directives of an unused range are both
+    /// reported.
+    pub fn unused_violations(&self, tables: &Tables, tree: &ErasedSegment) -> Vec<SQLLintError> {
+        let unused_usage = DirectiveUsage::default();
+        let usage = |idx: usize| self.usage.get(idx).unwrap_or(&unused_usage);
+
+        let mut enable_usage = vec![DirectiveUsage::default(); self.ignore_list.len()];
+        let mut all_rules_disabled_by = None;
+        let mut rules_disabled_by = HashMap::<&str, usize>::default();
+        for (idx, ignore) in self.range_directives() {
+            match ignore {
+                NoQADirective::RangeIgnoreAll(RangeIgnoreAll { action, .. }) => match action {
+                    IgnoreAction::Disable => all_rules_disabled_by = Some(idx),
+                    IgnoreAction::Enable => {
+                        if let Some(disable) = all_rules_disabled_by.take() {
+                            enable_usage[idx].masked = usage(disable).masked;
                         }
-                    },
-                    NoQADirective::RangeIgnoreRules(RangeIgnoreRules { action, rules, .. }) => {
+                    }
+                },
+                NoQADirective::RangeIgnoreRules(RangeIgnoreRules { action, rules, .. }) => {
+                    for rule in rules {
                         match action {
                             IgnoreAction::Disable => {
-                                for rule in rules {
-                                    disabled_rules.insert(rule.clone());
-                                }
+                                rules_disabled_by.insert(rule, idx);
                             }
                             IgnoreAction::Enable => {
-                                for rule in rules {
-                                    disabled_rules.remove(rule);
+                                if rules_disabled_by
+                                    .remove(rule.as_str())
+                                    .is_some_and(|disable| usage(disable).rules.contains(rule))
+                                {
+                                    enable_usage[idx].rules.insert(rule.clone());
                                 }
                             }
                         }
                     }
-                    _ => {}
                 }
+                _ => {}
             }
+        }
 
-            // Check whether the violation is masked
-            if all_rules_disabled {
-                return true;
-            } else if let Some(rule) = &violation.rule {
-                if disabled_rules.contains(rule.code) {
-                    return true;
+        let never_enabled = " It is never enabled again.";
+        let raw_segments = tree.get_raw_segments();
+        let mut violations = Vec::new();
+
+        for (idx, ignore) in self.ignore_list.iter().enumerate() {
+            // The description, and the rules to keep when only some are unused.
+            let (description, kept_rules) = match ignore {
+                NoQADirective::LineIgnoreAll(_) if !usage(idx).masked => {
+                    ("Unused 'noqa' comment.".to_string(), None)
                 }
-            }
+                NoQADirective::RangeIgnoreAll(RangeIgnoreAll { action, .. }) => match action {
+                    IgnoreAction::Disable if !usage(idx).masked => {
+                        let mut description = "Unused 'noqa: disable=all' range.".to_string();
+                        if all_rules_disabled_by == Some(idx) {
+                            description.push_str(never_enabled);
+                        }
+                        (description, None)
+                    }
+                    IgnoreAction::Enable if !enable_usage[idx].masked => (
+                        "'noqa: enable=all' doesn't end a used 'noqa: disable=all' range."
+                            .to_string(),
+                        None,
+                    ),
+                    _ => continue,
+                },
+                NoQADirective::LineIgnoreRules(LineIgnoreRules { raw_string, .. })
+                | NoQADirective::RangeIgnoreRules(RangeIgnoreRules { raw_string, .. }) => {
+                    let used = match ignore {
+                        NoQADirective::RangeIgnoreRules(RangeIgnoreRules {
+                            action: IgnoreAction::Enable,
+                            ..
+                        }) => &enable_usage[idx],
+                        _ => usage(idx),
+                    };
+                    let (kept, unused): (Vec<_>, Vec<_>) = directive_rules(raw_string)
+                        .into_iter()
+                        .partition(|rule| used.rules.contains(*rule));
+                    if unused.is_empty() {
+                        continue;
+                    }
+
+                    let (whole, part) = match ignore {
+                        NoQADirective::RangeIgnoreRules(RangeIgnoreRules {
+                            action: IgnoreAction::Disable,
+                            ..
+                        }) => (
+                            "Unused 'noqa: disable' range.".to_string(),
+                            format!(
+                                "Unused rules in 'noqa: disable' range: {}.",
+                                unused.join(",")
+                            ),
+                        ),
+                        NoQADirective::RangeIgnoreRules(_) => (
+                            "'noqa: enable' doesn't end a used 'noqa: disable' range.".to_string(),
+                            format!(
+                                "'noqa: enable' doesn't end a used 'noqa: disable' range for: {}.",
+                                unused.join(",")
+                            ),
+                        ),
+                        _ => (
+                            "Unused 'noqa' comment.".to_string(),
+                            format!("Unused rules in 'noqa' comment: {}.", unused.join(",")),
+                        ),
+                    };
+                    let mut description = if kept.is_empty() { whole } else { part };
+                    if unused
+                        .iter()
+                        .any(|rule| rules_disabled_by.get(rule) == Some(&idx))
+                    {
+                        description.push_str(never_enabled);
+                    }
+
+                    (description, (!kept.is_empty()).then_some(kept))
+                }
+                _ => continue,
+            };
 
-            false
+            let Some(comment_idx) = raw_segments.iter().position(|segment| {
+                COMMENT_TYPES.contains(segment.get_type())
+                    && segment
+                        .get_position_marker()
+                        .is_some_and(|marker| marker.source_position() == ignore.position())
+            }) else {
+                continue;
+            };
+            let comment = &raw_segments[comment_idx];
+
+            let fixes = match kept_rules {
+                Some(kept) => {
+                    let raw = comment.raw();
+                    let start = raw.rfind(['=', ':']).map_or(0, |idx| idx + 1);
+                    let rules = raw[start..].trim_end();
+                    let rules = rules.strip_suffix("*/").unwrap_or(rules).trim_end();
+                    let leading = rules.len() - rules.trim_start().len();
+                    let end = start + rules.len();
+                    let raw = format!(
+                        "{}{}{}",
+                        &raw[..start + leading],
+                        kept.join(","),
+                        &raw[end..]
+                    );
+                    vec![LintFix::replace(
+                        comment.clone(),
+                        vec![comment.edit(tables.next_id(), Some(raw), None)],
+                        None,
+                    )]
+                }
+                None => removal_fixes(tables, &raw_segments, comment_idx),
+            };
+
+            let mut violation = SQLLintError::new(&description, comment.clone(), true, fixes);
+            violation.rule = Some(UNUSED_NOQA_RULE);
+            violation.severity = Severity::Warning;
+            violations.push(violation);
         }
 
-        is_masked_by_line_rules(self, violation) || is_masked_by_range_rules(self, violation)
+        violations
     }
 }
 
+/// The rules of a directive, in the order they are written.
+fn directive_rules(raw_string: &str) -> Vec<&str> {
+    let rules = raw_string.rsplit(['=', ':']).next().unwrap_or_default();
+    rules
+        .split(',')
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .unique()
+        .collect()
+}
+
+/// The fixes removing the directive of a comment, with the comment itself and
+/// the whitespace around it unless it follows another comment.
+fn removal_fixes(
+    tables: &Tables,
+    raw_segments: &[ErasedSegment],
+    comment_idx: usize,
+) -> Vec<LintFix> {
+    let comment = &raw_segments[comment_idx];
+    let raw = comment.raw();
+
+    // Keep a comment before the directive, as in `-- note -- noqa`.
+    let prefix = raw[..raw.rfind("--").unwrap_or(0)].trim_end();
+    if !prefix.is_empty() && prefix != "/*" {
+        let raw = if raw.starts_with("/*") {
+            format!("{prefix} */")
+        } else {
+            prefix.to_string()
+        };
+        return vec![LintFix::replace(
+            comment.clone(),
+            vec![comment.edit(tables.next_id(), Some(raw), None)],
+            None,
+        )];
+    }
+
+    let mut fixes = vec![LintFix::delete(comment.clone())];
+    let mut own_line = true;
+    for segment in raw_segments[..comment_idx]
+        .iter()
+        .rev()
+        .filter(|segment| !segment.is_meta())
+    {
+        if segment.is_type(SyntaxKind::Whitespace) {
+            fixes.push(LintFix::delete(segment.clone()));
+        } else {
+            own_line = segment.is_type(SyntaxKind::Newline);
+            break;
+        }
+    }
+
+    // A comment on a line of its own is removed with its line.
+    if own_line {
+        if let Some(newline) = raw_segments[comment_idx + 1..]
+            .iter()
+            .find(|segment| !segment.is_meta())
+            .filter(|segment| segment.is_type(SyntaxKind::Newline))
+        {
+            fixes.push(LintFix::delete(newline.clone()));
+        }
+    }
+
+    fixes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,7 +743,7 @@ mod tests {
         let error = SQLBaseError {
             fatal: false,
             ignore: false,
-            warning: false,
+            severity: Severity::Error,
             line_no: 2,
             line_pos: 11,
             description: "Implicit/explicit aliasing of columns.".to_string(),
@@ -492,6 +753,7 @@ mod tests {
             }),
             source_slice: Default::default(),
             fixable: true,
+            templated: false,
         };
         let mask = IgnoreMask {
             ignore_list: vec![NoQADirective::LineIgnoreRules(LineIgnoreRules {
@@ -500,6 +762,7 @@ mod tests {
                 raw_string: "--noqa: AL02".to_string(),
                 rules: ["AL02".to_string()].into_iter().collect(),
             })],
+            usage: Vec::new(),
         };
         let not_mask_wrong_line = IgnoreMask {
             ignore_list: vec![NoQADirective::LineIgnoreRules(LineIgnoreRules {
@@ -508,6 +771,7 @@ mod tests {
                 raw_string: "--noqa: AL02".to_string(),
                 rules: ["AL02".to_string()].into_iter().collect(),
             })],
+            usage: Vec::new(),
         };
         let not_mask_wrong_rule = IgnoreMask {
             ignore_list: vec![NoQADirective::LineIgnoreRules(LineIgnoreRules {
@@ -516,6 +780,7 @@ mod tests {
                 raw_string: "--noqa: AL03".to_string(),
                 rules: ["AL03".to_string()].into_iter().collect(),
             })],
+            usage: Vec::new(),
         };
 
         assert!(!not_mask_wrong_line.is_masked(&error));
@@ -733,4 +998,87 @@ FROM foo
         assert_eq!(result_rule.get_violations(None).len(), 3);
         assert_eq!(result_all.get_violations(None).len(), 3);
     }
+
+    #[test]
+    fn test_linter_unused_noqa() {
+        let linter = Linter::new(
+            FluffConfig::from_source(
+                r#"
+[sqruff]
+dialect = bigquery
+rules = AL02
+warn_unused_ignores = True
+    "#,
+                None,
+            ),
+            None,
+            None,
+            false,
+        );
+
+        let sql = r#"-- noqa: disable=CP01
+SELECT
+    col_a a, -- noqa: AL02,LT01
+    col_b AS b, -- noqa
+    col_c c, -- note -- noqa: AL02
+    col_d AS d, -- noqa: disable=all
+    col_e AS e -- noqa: enable=all
+FROM foo
+"#;
+
+        let result = linter.lint_string(sql, None, false);
+        let violations = result
+            .get_violations(None)
+            .into_iter()
+            .map(|v| (v.line_no, v.rule_code(), v.description, v.severity))
+            .collect_vec();
+        assert_eq!(
+            violations,
+            [
+                (
+                    1,
+                    "NOQA",
+                    "Unused 'noqa: disable' range. It is never enabled again.".to_string(),
+                    Severity::Warning
+                ),
+                (
+                    3,
+                    "NOQA",
+                    "Unused rules in 'noqa' comment: LT01.".to_string(),
+                    Severity::Warning
+                ),
+                (
+                    4,
+                    "NOQA",
+                    "Unused 'noqa' comment.".to_string(),
+                    Severity::Warning
+                ),
+                (
+                    6,
+                    "NOQA",
+                    "Unused 'noqa: disable=all' range.".to_string(),
+                    Severity::Warning
+                ),
+                (
+                    7,
+                    "NOQA",
+                    "'noqa: enable=all' doesn't end a used 'noqa: disable=all' range.".to_string(),
+                    Severity::Warning
+                ),
+            ]
+        );
+
+        let result = linter.lint_string(sql, None, true);
+        assert_eq!(
+            result.fix_string(),
+            r#"SELECT
+    col_a a, -- noqa: AL02
+    col_b AS b,
+    col_c c, -- note -- noqa: AL02
+    col_d AS d,
+    col_e AS e
+FROM foo
+"#
+        );
+    }
 }
@@ -1,15 +1,20 @@
 use itertools::{chain, Itertools};
 use sqruff_lib_core::helpers::IndexMap;
 
-use crate::core::rules::base::{ErasedRule, RuleManifest, RuleSet};
+use crate::core::rules::base::{ErasedRule, RuleSet};
 
 pub mod aliasing;
 pub mod ambiguous;
 pub mod capitalisation;
 pub mod convention;
 pub mod layout;
+pub mod migration;
+pub mod performance;
 pub mod references;
+pub mod safety;
 pub mod structure;
+pub mod types;
+pub mod window;
 
 pub fn rules() -> Vec<ErasedRule> {
     chain!(
@@ -18,32 +23,29 @@ pub fn rules() -> Vec<ErasedRule> {
         capitalisation::rules(),
         convention::rules(),
         layout::rules(),
+        migration::rules(),
+        performance::rules(),
         references::rules(),
-        structure::rules()
+        safety::rules(),
+        structure::rules(),
+        types::rules(),
+        window::rules()
     )
     .collect_vec()
 }
 
 pub fn get_ruleset() -> RuleSet {
-    let mut register = IndexMap::default();
-
     let rules = rules();
-    register.reserve(rules.len());
+    let mut ruleset = RuleSet {
+        register: IndexMap::default(),
+    };
+    ruleset.register.reserve(rules.len());
 
     for rule in rules {
-        register.insert(
-            rule.code(),
-            RuleManifest {
-                code: rule.code(),
-                name: rule.name(),
-                description: rule.description(),
-                groups: rule.groups(),
-                rule_class: rule,
-            },
-        );
+        ruleset.register(rule);
     }
 
-    RuleSet { register }
+    ruleset
 }
 
 #[cfg(test)]
@@ -11,6 +11,8 @@ pub mod cv08;
 pub mod cv09;
 pub mod cv10;
 pub mod cv11;
+pub mod cv12;
+pub mod cv13;
 
 pub fn rules() -> Vec<ErasedRule> {
     use crate::core::rules::base::Erased as _;
@@ -27,5 +29,7 @@ pub fn rules() -> Vec<ErasedRule> {
         cv09::RuleCV09::default().erased(),
         cv10::RuleCV10::default().erased(),
         cv11::RuleCV11::default().erased(),
+        cv12::RuleCV12::default().erased(),
+        cv13::RuleCV13::default().erased(),
     ]
 }
@@ -0,0 +1,355 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::lexer::StringOrTemplate;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, SegmentBuilder};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::utils::types::function_name;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleCV12 {
+    /// Replacement templates by uppercased function name, or `None` for
+    /// functions which are banned without a replacement.
+    replacements: AHashMap<String, Option<String>>,
+    use_presets: bool,
+}
+
+impl Rule for RuleCV12 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let replacements = config
+            .get("replacements")
+            .and_then(Value::as_map)
+            .into_iter()
+            .flatten()
+            .map(|(name, template)| {
+                let template = template
+                    .as_string()
+                    .map(str::trim)
+                    .filter(|it| !it.is_empty())
+                    .map(ToOwned::to_owned);
+                if let Some(template) = &template {
+                    highest_placeholder(template).map_err(|placeholder| {
+                        format!(
+                            "The replacement of '{name}' has the placeholder '${placeholder}', \
+                             which doesn't refer to an argument"
+                        )
+                    })?;
+                }
+                Ok((name.trim().to_uppercase(), template))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(RuleCV12 {
+            replacements,
+            use_presets: config
+                .get("use_presets")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "convention.banned_functions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Banned functions should be replaced with their configured equivalents."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A function is used which has been banned, usually because it's specific to one database and has a portable equivalent.
+
+```sql
+SELECT NVL(discount, 0), GETDATE()
+FROM orders
+```
+
+**Best practice**
+
+Use the replacement configured for the function.
+
+```sql
+SELECT COALESCE(discount, 0), CURRENT_TIMESTAMP
+FROM orders
+```
+
+Replacements are configured by function name in the `replacements` subsection of this rule. A replacement is either a function name, which renames the function and keeps its arguments, a bare function such as `CURRENT_TIMESTAMP`, which replaces the whole call, or a template in which `$1`, `$2` and so on stand for the arguments of the call. Calls with more arguments than the template uses are reported without a fix. A function with an empty replacement is reported without a fix.
+
+```ini
+[sqruff:rules:convention.banned_functions:replacements]
+nvl = COALESCE
+dateadd = TIMESTAMPADD($1, $2, $3)
+to_varchar =
+```
+
+With `use_presets` set, common portability rewrites for the dialect are applied too, such as `IFNULL` and `NVL` to `COALESCE`. Configured replacements take precedence over the presets.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Convention]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let name = function_name(&context.segment);
+        let template = match self.replacements.get(&name) {
+            Some(template) => template.as_deref(),
+            None if self.use_presets => {
+                match presets(context.dialect.name)
+                    .iter()
+                    .find(|(banned, _)| *banned == name)
+                {
+                    Some((_, template)) => Some(*template),
+                    None => return Vec::new(),
+                }
+            }
+            None => return Vec::new(),
+        };
+
+        let Some(template) = template else {
+            return vec![LintResult::new(
+                context.segment.clone().into(),
+                Vec::new(),
+                Some(format!("Function '{name}' is banned.")),
+                None,
+            )];
+        };
+
+        let fixes = if !is_function_name(template) {
+            expand(context, template)
+        } else if is_bare_function(context, template) {
+            // Bare functions such as `CURRENT_TIMESTAMP` are written without
+            // brackets.
+            vec![LintFix::replace(
+                context.segment.clone(),
+                vec![SegmentBuilder::token(
+                    context.tables.next_id(),
+                    template,
+                    SyntaxKind::BareFunction,
+                )
+                .finish()],
+                None,
+            )]
+        } else {
+            rename(context, template)
+        };
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            fixes,
+            Some(format!("Use '{template}' instead of '{name}'.")),
+            None,
+        )]
+    }
+
+    fn is_fix_compatible(&self) -> bool {
+        true
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::new(&[SyntaxKind::Function]) }).into()
+    }
+}
+
+/// Built-in replacements for functions which have portable equivalents.
+fn presets(dialect: DialectKind) -> &'static [(&'static str, &'static str)] {
+    match dialect {
+        DialectKind::Bigquery => &[
+            ("IFNULL", "COALESCE"),
+            ("IF", "CASE WHEN $1 THEN $2 ELSE $3 END"),
+        ],
+        DialectKind::Postgres | DialectKind::Trino | DialectKind::Athena => {
+            &[("NOW", "CURRENT_TIMESTAMP")]
+        }
+        DialectKind::Redshift => &[
+            ("NVL", "COALESCE"),
+            ("NVL2", "CASE WHEN $1 IS NOT NULL THEN $2 ELSE $3 END"),
+            ("GETDATE", "CURRENT_TIMESTAMP"),
+        ],
+        DialectKind::Snowflake => &[
+            ("NVL", "COALESCE"),
+            ("IFNULL", "COALESCE"),
+            ("NVL2", "CASE WHEN $1 IS NOT NULL THEN $2 ELSE $3 END"),
+            ("IFF", "CASE WHEN $1 THEN $2 ELSE $3 END"),
+            ("GETDATE", "CURRENT_TIMESTAMP"),
+        ],
+        DialectKind::Sparksql | DialectKind::Databricks => &[
+            ("NVL", "COALESCE"),
+            ("IFNULL", "COALESCE"),
+            ("NVL2", "CASE WHEN $1 IS NOT NULL THEN $2 ELSE $3 END"),
+        ],
+        DialectKind::Ansi | DialectKind::Clickhouse | DialectKind::Duckdb | DialectKind::Sqlite => {
+            &[("IFNULL", "COALESCE"), ("NVL", "COALESCE")]
+        }
+    }
+}
+
+fn is_function_name(template: &str) -> bool {
+    template
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
+fn is_bare_function(context: &RuleContext, name: &str) -> bool {
+    context
+        .dialect
+        .sets("bare_functions")
+        .iter()
+        .any(|it| it.eq_ignore_ascii_case(name))
+}
+
+/// Replaces the name of the function, keeping its arguments.
+fn rename(context: &RuleContext, name: &str) -> Vec<LintFix> {
+    let Some(identifier) = context
+        .segment
+        .child(const { &SyntaxSet::single(SyntaxKind::FunctionName) })
+    else {
+        return Vec::new();
+    };
+
+    vec![LintFix::replace(
+        identifier,
+        vec![SegmentBuilder::node(
+            context.tables.next_id(),
+            SyntaxKind::FunctionName,
+            context.dialect.name,
+            vec![SegmentBuilder::token(
+                context.tables.next_id(),
+                name,
+                SyntaxKind::FunctionNameIdentifier,
+            )
+            .finish()],
+        )
+        .finish()],
+        None,
+    )]
+}
+
+/// Replaces the whole call with `template`, substituting its `$n`
+/// placeholders with the arguments of the call. There is no fix if the
+/// template refers to an argument which the call doesn't have, or leaves out
+/// one which it has.
+fn expand(context: &RuleContext, template: &str) -> Vec<LintFix> {
+    let arguments = arguments(&context.segment);
+    if !highest_placeholder(template).is_ok_and(|highest| arguments.len() <= highest) {
+        return Vec::new();
+    }
+
+    let mut edit = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('$') {
+        let digits = rest[start + 1..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if digits == 0 {
+            edit.extend(tokens(context, &rest[..=start]));
+            rest = &rest[start + 1..];
+            continue;
+        }
+
+        let Some(argument) = rest[start + 1..start + 1 + digits]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| arguments.get(index))
+        else {
+            return Vec::new();
+        };
+
+        edit.extend(tokens(context, &rest[..start]));
+        edit.extend(argument.iter().cloned());
+        rest = &rest[start + 1 + digits..];
+    }
+    edit.extend(tokens(context, rest));
+
+    vec![LintFix::replace(context.segment.clone(), edit, None)]
+}
+
+/// The highest `$n` placeholder of a template, or the first placeholder
+/// which can't refer to an argument, such as `$0`.
+fn highest_placeholder(template: &str) -> Result<usize, &str> {
+    let mut highest = 0;
+    let mut rest = template;
+
+    while let Some(start) = rest.find('$') {
+        rest = &rest[start + 1..];
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            continue;
+        }
+
+        let placeholder = &rest[..digits];
+        match placeholder.parse::<usize>() {
+            Ok(index) if index > 0 => highest = highest.max(index),
+            _ => return Err(placeholder),
+        }
+        rest = &rest[digits..];
+    }
+
+    Ok(highest)
+}
+
+/// The segments of each argument of a call, without surrounding whitespace.
+fn arguments(function: &ErasedSegment) -> Vec<Vec<ErasedSegment>> {
+    let Some(bracketed) = function.child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+    else {
+        return Vec::new();
+    };
+
+    let inner = bracketed
+        .segments()
+        .iter()
+        .filter(|it| {
+            !matches!(
+                it.get_type(),
+                SyntaxKind::StartBracket | SyntaxKind::EndBracket
+            )
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    inner
+        .split(|it| it.is_type(SyntaxKind::Comma))
+        .map(|argument| {
+            let start = argument.iter().position(|it| it.is_code());
+            let end = argument.iter().rposition(|it| it.is_code());
+            match (start, end) {
+                (Some(start), Some(end)) => argument[start..=end].to_vec(),
+                _ => Vec::new(),
+            }
+        })
+        .filter(|it| !it.is_empty())
+        .collect()
+}
+
+/// Lexes part of a template into new tokens.
+fn tokens(context: &RuleContext, sql: &str) -> Vec<ErasedSegment> {
+    if sql.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok((tokens, _)) = context
+        .dialect
+        .lexer()
+        .lex(context.tables, StringOrTemplate::String(sql))
+    else {
+        return Vec::new();
+    };
+
+    tokens
+        .iter()
+        .filter(|it| !it.is_type(SyntaxKind::EndOfFile))
+        .map(|it| SegmentBuilder::token(context.tables.next_id(), it.raw(), it.get_type()).finish())
+        .collect()
+}
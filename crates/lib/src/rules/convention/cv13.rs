@@ -0,0 +1,315 @@
+use ahash::{AHashMap, AHashSet};
+use smol_str::SmolStr;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use strum_macros::{AsRefStr, EnumString};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, RootOnlyCrawler};
+use crate::rules::safety::normalise;
+
+/// Where the comment describing a table or view can be written.
+#[derive(Debug, Copy, Clone, AsRefStr, EnumString, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+enum CommentLocation {
+    /// In the `CREATE` statement, such as `COMMENT = '...'`.
+    Inline,
+    /// In a `COMMENT ON TABLE` or `COMMENT ON VIEW` statement in the same
+    /// file.
+    CommentOn,
+    /// In a `--` or `/* */` comment on the lines before the `CREATE`
+    /// statement.
+    Header,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleCV13 {
+    comment_locations: Vec<CommentLocation>,
+    require_column_comments: bool,
+}
+
+impl Default for RuleCV13 {
+    fn default() -> Self {
+        Self {
+            comment_locations: vec![
+                CommentLocation::Inline,
+                CommentLocation::CommentOn,
+                CommentLocation::Header,
+            ],
+            require_column_comments: false,
+        }
+    }
+}
+
+impl Rule for RuleCV13 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let comment_locations = match config.get("comment_locations") {
+            Some(Value::None) | None => Self::default().comment_locations,
+            Some(value) => value
+                .as_array()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|it| {
+                    let location = it.as_string().unwrap_or_default().trim();
+                    location.parse().map_err(|_| {
+                        format!(
+                            "comment_locations must be a list of inline, comment_on and header, \
+                             got {location:?}"
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(RuleCV13 {
+            comment_locations,
+            require_column_comments: config
+                .get("require_column_comments")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "convention.object_comments"
+    }
+
+    fn description(&self) -> &'static str {
+        "Created tables and views should have a comment."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A table or view is created without a description, so users of the warehouse have to guess what it contains.
+
+```sql
+CREATE TABLE orders (
+    id INT,
+    amount NUMBER(10, 2)
+)
+```
+
+**Best practice**
+
+Describe the table, and optionally its columns.
+
+```sql
+CREATE TABLE orders (
+    id INT COMMENT 'Unique identifier of the order',
+    amount NUMBER(10, 2) COMMENT 'Total amount charged, in dollars'
+)
+COMMENT = 'One row per order placed in the web shop'
+```
+
+The places a table comment is accepted are set with `comment_locations`, a list of:
+
+* `inline`, a comment in the `CREATE` statement, such as `COMMENT = '...'` or BigQuery's `OPTIONS(description = '...')`.
+* `comment_on`, a `COMMENT ON TABLE` or `COMMENT ON VIEW` statement in the same file.
+* `header`, a `--` or `/* */` comment on the lines directly before the `CREATE` statement.
+
+When `require_column_comments` is enabled, every column of a created table also needs an inline comment or a `COMMENT ON COLUMN` statement. Columns are only checked on dialects with inline column comments, which are BigQuery, Databricks, Snowflake and Spark SQL.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Convention]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let statements = context
+            .segment
+            .recursive_crawl(
+                const { &SyntaxSet::single(SyntaxKind::Statement) },
+                false,
+                &SyntaxSet::EMPTY,
+                true,
+            )
+            .into_iter()
+            .filter_map(|it| it.segments().iter().find(|it| it.is_code()).cloned())
+            .collect::<Vec<_>>();
+
+        let mut commented_objects = AHashSet::new();
+        let mut commented_columns = AHashSet::new();
+        for statement in &statements {
+            let Some((object, name)) = comment_on(statement) else {
+                continue;
+            };
+
+            if object == "COLUMN" {
+                commented_columns.insert(name);
+            } else {
+                commented_objects.insert(name);
+            }
+        }
+
+        let mut results = Vec::new();
+        for statement in &statements {
+            let object = match statement.get_type() {
+                SyntaxKind::CreateTableStatement => "table",
+                SyntaxKind::CreateViewStatement => "view",
+                _ => continue,
+            };
+            let Some(reference) = object_reference(statement) else {
+                continue;
+            };
+            let name = normalise(reference.raw());
+
+            let commented = self.comment_locations.iter().any(|location| match location {
+                CommentLocation::Inline => has_inline_comment(statement),
+                CommentLocation::CommentOn => commented_objects.contains(&name),
+                CommentLocation::Header => has_header_comment(&context.segment, statement),
+            });
+            if !commented {
+                results.push(LintResult::new(
+                    statement.clone().into(),
+                    Vec::new(),
+                    Some(format!("Missing comment on {object} '{}'.", reference.raw())),
+                    None,
+                ));
+            }
+
+            if !self.require_column_comments
+                || object != "table"
+                || !matches!(
+                    context.dialect.name,
+                    DialectKind::Bigquery
+                        | DialectKind::Databricks
+                        | DialectKind::Snowflake
+                        | DialectKind::Sparksql
+                )
+            {
+                continue;
+            }
+
+            for (column, commented) in column_definitions(statement) {
+                let Some(column_name) = column.segments().iter().find(|it| it.is_code()) else {
+                    continue;
+                };
+                let qualified = format!("{name}.{}", normalise(column_name.raw()));
+
+                if !commented && !commented_columns.contains(qualified.as_str()) {
+                    results.push(LintResult::new(
+                        column.clone().into(),
+                        Vec::new(),
+                        Some(format!(
+                            "Missing comment on column '{}' of table '{}'.",
+                            column_name.raw(),
+                            reference.raw()
+                        )),
+                        None,
+                    ));
+                }
+            }
+        }
+
+        results
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        RootOnlyCrawler.into()
+    }
+}
+
+/// The kind of object and the name of the object described by a `COMMENT
+/// ON` statement.
+fn comment_on(statement: &ErasedSegment) -> Option<(String, SmolStr)> {
+    if !statement.is_type(SyntaxKind::CommentStatement)
+        && !statement.is_type(SyntaxKind::CommentClause)
+    {
+        return None;
+    }
+
+    let code = statement
+        .segments()
+        .iter()
+        .filter(|it| it.is_code())
+        .collect::<Vec<_>>();
+    if !code.first()?.is_keyword("COMMENT") || !code.get(1)?.is_keyword("ON") {
+        return None;
+    }
+
+    let object = code.get(2)?.raw().to_uppercase();
+    let name = code.get(3)?;
+    Some((object, normalise(name.raw())))
+}
+
+/// The reference to the table or view created by a statement.
+fn object_reference(statement: &ErasedSegment) -> Option<ErasedSegment> {
+    statement
+        .child(const { &SyntaxSet::new(&[SyntaxKind::TableReference, SyntaxKind::ObjectReference]) })
+}
+
+/// Whether a `CREATE` statement or column definition has a comment of its
+/// own, such as `COMMENT = '...'` or `OPTIONS(description = '...')`.
+fn has_inline_comment(segment: &ErasedSegment) -> bool {
+    segment.segments().iter().any(|it| match it.get_type() {
+        SyntaxKind::CommentClause | SyntaxKind::CommentEqualsClause => true,
+        SyntaxKind::ColumnConstraintSegment => has_inline_comment(it),
+        SyntaxKind::OptionsSegment => it.recursive_crawl_all(false).iter().any(|it| {
+            it.is_type(SyntaxKind::Parameter) && it.raw().eq_ignore_ascii_case("description")
+        }),
+        _ => it.is_keyword("COMMENT"),
+    })
+}
+
+/// Whether a statement directly follows a comment which starts on its own
+/// line, with nothing but whitespace between them.
+fn has_header_comment(file: &ErasedSegment, statement: &ErasedSegment) -> bool {
+    let raw_segments = file.get_raw_segments();
+    let Some(first) = statement.get_raw_segments().into_iter().next() else {
+        return false;
+    };
+    let Some(position) = raw_segments.iter().position(|it| it.id() == first.id()) else {
+        return false;
+    };
+
+    let mut before = raw_segments[..position]
+        .iter()
+        .rev()
+        .filter(|it| !it.is_type(SyntaxKind::Whitespace) && !it.is_meta());
+    // At most one newline may separate the comment from the statement.
+    let mut next = before.next();
+    if next.is_some_and(|it| it.is_type(SyntaxKind::Newline)) {
+        next = before.next();
+    }
+    if !next.is_some_and(|it| it.is_comment()) {
+        return false;
+    }
+
+    before
+        .next()
+        .map_or(true, |it| it.is_type(SyntaxKind::Newline))
+}
+
+/// The column definitions of a `CREATE TABLE` statement, and whether each
+/// has a comment. Snowflake parses the comment of a column as the next
+/// element of the column list.
+fn column_definitions(statement: &ErasedSegment) -> Vec<(ErasedSegment, bool)> {
+    let Some(bracketed) = statement.child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+    else {
+        return Vec::new();
+    };
+
+    let mut columns: Vec<(ErasedSegment, bool)> = Vec::new();
+    for segment in bracketed.segments() {
+        match segment.get_type() {
+            SyntaxKind::ColumnDefinition => {
+                columns.push((segment.clone(), has_inline_comment(segment)));
+            }
+            SyntaxKind::CommentClause => {
+                if let Some((_, commented)) = columns.last_mut() {
+                    *commented = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    columns
+}
+
@@ -0,0 +1,87 @@
+use std::sync::LazyLock;
+
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use strum::IntoEnumIterator;
+
+use crate::core::rules::base::ErasedRule;
+use crate::core::rules::context::RuleContext;
+use crate::rules::safety::{normalise, target_reference, Allowlist};
+
+pub mod mg01;
+pub mod mg02;
+pub mod mg03;
+pub mod mg04;
+pub mod mg05;
+pub mod mg06;
+pub mod mg07;
+
+pub fn rules() -> Vec<ErasedRule> {
+    use crate::core::rules::base::Erased as _;
+
+    vec![
+        mg01::RuleMG01::default().erased(),
+        mg02::RuleMG02::default().erased(),
+        mg03::RuleMG03::default().erased(),
+        mg04::RuleMG04::default().erased(),
+        mg05::RuleMG05::default().erased(),
+        mg06::RuleMG06::default().erased(),
+        mg07::RuleMG07::default().erased(),
+    ]
+}
+
+/// Every dialect except postgres. Redshift and DuckDB reuse much of the
+/// postgres grammar, but not its locking behaviour.
+pub(crate) static NON_POSTGRES_DIALECTS: LazyLock<Vec<DialectKind>> = LazyLock::new(|| {
+    DialectKind::iter()
+        .filter(|&dialect| dialect != DialectKind::Postgres)
+        .collect()
+});
+/// Whether the lock taken by `statement` can't block anyone, either because
+/// its table is allowlisted or because the table is created in the same file.
+pub(crate) fn is_exempt(
+    context: &RuleContext,
+    allowlist: &Allowlist,
+    statement: &ErasedSegment,
+) -> bool {
+    allowlist.allows(statement) || is_created_in_file(context, statement)
+}
+
+fn is_created_in_file(context: &RuleContext, statement: &ErasedSegment) -> bool {
+    let Some(reference) = target_reference(statement) else {
+        return false;
+    };
+    let name = normalise(reference.raw());
+
+    let root = context.parent_stack.first().unwrap_or(&context.segment);
+    root.recursive_crawl(
+        const { &SyntaxSet::single(SyntaxKind::CreateTableStatement) },
+        false,
+        &SyntaxSet::EMPTY,
+        true,
+    )
+    .into_iter()
+    .filter_map(|create| target_reference(&create))
+    .any(|created| normalise(created.raw()) == name)
+}
+
+/// The code children of `segment`.
+pub(crate) fn code_children(segment: &ErasedSegment) -> Vec<ErasedSegment> {
+    segment
+        .segments()
+        .iter()
+        .filter(|it| it.is_code())
+        .cloned()
+        .collect()
+}
+
+/// Whether `keywords` appear consecutively among `segments`.
+pub(crate) fn has_keyword_sequence(segments: &[ErasedSegment], keywords: &[&str]) -> bool {
+    segments.windows(keywords.len()).any(|window| {
+        window
+            .iter()
+            .zip(keywords)
+            .all(|(segment, keyword)| segment.is_keyword(keyword))
+    })
+}
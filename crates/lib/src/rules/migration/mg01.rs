@@ -0,0 +1,86 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::{is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG01 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG01 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG01 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.create_index_concurrently"
+    }
+
+    fn description(&self) -> &'static str {
+        "'CREATE INDEX' should use 'CONCURRENTLY'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A plain `CREATE INDEX` holds a `SHARE` lock on the table until the index is built, blocking every insert, update and delete for the duration.
+
+```sql
+CREATE INDEX orders_customer_id_idx ON orders (customer_id)
+```
+
+**Best practice**
+
+Build the index with `CONCURRENTLY`. It takes longer and can't run inside a transaction, but writes continue while it runs.
+
+```sql
+CREATE INDEX CONCURRENTLY orders_customer_id_idx ON orders (customer_id)
+```
+
+Tables created in the same file, or listed in `allowlist`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let concurrently = context
+            .segment
+            .segments()
+            .iter()
+            .any(|it| it.is_keyword("CONCURRENTLY"));
+
+        if concurrently || is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            Vec::new(),
+            None,
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::CreateIndexStatement) })
+            .into()
+    }
+}
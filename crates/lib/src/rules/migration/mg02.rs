@@ -0,0 +1,115 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use super::{code_children, has_keyword_sequence, is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG02 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG02 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG02 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.add_column_not_null"
+    }
+
+    fn description(&self) -> &'static str {
+        "Columns added as 'NOT NULL' should have a default."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Adding a `NOT NULL` column without a default fails on any table which already has rows, and a volatile default forces a rewrite of the table under an `ACCESS EXCLUSIVE` lock.
+
+```sql
+ALTER TABLE orders ADD COLUMN status text NOT NULL
+```
+
+**Best practice**
+
+Give the column a constant default, which postgres 11 and later applies without rewriting the table.
+
+```sql
+ALTER TABLE orders ADD COLUMN status text NOT NULL DEFAULT 'new'
+```
+
+Tables created in the same file, or listed in `allowlist`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let actions: Vec<_> = context
+            .segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_type(SyntaxKind::AlterTableActionSegment))
+            .filter(|it| is_not_null_without_default(it))
+            .cloned()
+            .collect();
+
+        if actions.is_empty() || is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        actions
+            .into_iter()
+            .map(|action| LintResult::new(action.into(), Vec::new(), None, None))
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AlterTableStatement) })
+            .into()
+    }
+}
+
+fn is_not_null_without_default(action: &ErasedSegment) -> bool {
+    let children = code_children(action);
+    let adds_column = children.first().is_some_and(|it| it.is_keyword("ADD"))
+        && children
+            .iter()
+            .any(|it| it.is_type(SyntaxKind::ColumnReference));
+
+    if !adds_column {
+        return false;
+    }
+
+    let constraints: Vec<_> = children
+        .iter()
+        .filter(|it| it.is_type(SyntaxKind::ColumnConstraintSegment))
+        .map(code_children)
+        .collect();
+
+    let not_null = constraints
+        .iter()
+        .any(|it| has_keyword_sequence(it, &["NOT", "NULL"]));
+    let default = constraints
+        .iter()
+        .any(|it| it.first().is_some_and(|it| it.is_keyword("DEFAULT")));
+
+    not_null && !default
+}
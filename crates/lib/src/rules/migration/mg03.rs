@@ -0,0 +1,94 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use super::{code_children, is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG03 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG03 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG03 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.alter_column_type"
+    }
+
+    fn description(&self) -> &'static str {
+        "Changing the type of a column rewrites the table."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Most column type changes rewrite the whole table and rebuild its indexes while holding an `ACCESS EXCLUSIVE` lock, which blocks reads as well as writes.
+
+```sql
+ALTER TABLE orders ALTER COLUMN total TYPE numeric(12, 2)
+```
+
+**Best practice**
+
+Add a new column with the desired type, backfill it in batches, and switch readers over before dropping the old column.
+
+```sql
+ALTER TABLE orders ADD COLUMN total_numeric numeric(12, 2)
+```
+
+Tables created in the same file, or listed in `allowlist`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let actions: Vec<_> = context
+            .segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_type(SyntaxKind::AlterTableActionSegment))
+            .filter(|it| is_type_change(it))
+            .cloned()
+            .collect();
+
+        if actions.is_empty() || is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        actions
+            .into_iter()
+            .map(|action| LintResult::new(action.into(), Vec::new(), None, None))
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AlterTableStatement) })
+            .into()
+    }
+}
+
+fn is_type_change(action: &ErasedSegment) -> bool {
+    let children = code_children(action);
+    children.first().is_some_and(|it| it.is_keyword("ALTER"))
+        && children.iter().any(|it| it.is_keyword("TYPE"))
+}
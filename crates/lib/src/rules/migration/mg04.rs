@@ -0,0 +1,98 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use super::{code_children, has_keyword_sequence, is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG04 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG04 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG04 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.foreign_key_not_valid"
+    }
+
+    fn description(&self) -> &'static str {
+        "Foreign keys added to existing tables should be 'NOT VALID'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Adding a foreign key scans the whole table to validate existing rows while holding a `SHARE ROW EXCLUSIVE` lock on both tables, blocking writes to each.
+
+```sql
+ALTER TABLE orders
+ADD CONSTRAINT orders_customer_fk FOREIGN KEY (customer_id) REFERENCES customers (id)
+```
+
+**Best practice**
+
+Add the constraint as `NOT VALID`, which only checks new rows, then validate it separately with a weaker lock.
+
+```sql
+ALTER TABLE orders
+ADD CONSTRAINT orders_customer_fk FOREIGN KEY (customer_id) REFERENCES customers (id) NOT VALID;
+
+ALTER TABLE orders VALIDATE CONSTRAINT orders_customer_fk
+```
+
+Tables created in the same file, or listed in `allowlist`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let constraints: Vec<_> = context
+            .segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_type(SyntaxKind::AlterTableActionSegment))
+            .filter_map(|it| it.child(const { &SyntaxSet::single(SyntaxKind::TableConstraint) }))
+            .filter(is_validated_foreign_key)
+            .collect();
+
+        if constraints.is_empty() || is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        constraints
+            .into_iter()
+            .map(|constraint| LintResult::new(constraint.into(), Vec::new(), None, None))
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AlterTableStatement) })
+            .into()
+    }
+}
+
+fn is_validated_foreign_key(constraint: &ErasedSegment) -> bool {
+    let children = code_children(constraint);
+    has_keyword_sequence(&children, &["FOREIGN", "KEY"])
+        && !has_keyword_sequence(&children, &["NOT", "VALID"])
+}
@@ -0,0 +1,102 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use super::{code_children, has_keyword_sequence, is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG05 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG05 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG05 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.unique_constraint_index"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unique constraints should be added using an existing index."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Adding a `UNIQUE` constraint builds its index while holding an `ACCESS EXCLUSIVE` lock on the table, blocking reads and writes until the build finishes.
+
+```sql
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email)
+```
+
+**Best practice**
+
+Build a unique index concurrently first, then attach it to the constraint.
+
+```sql
+CREATE UNIQUE INDEX CONCURRENTLY users_email_idx ON users (email);
+
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE USING INDEX users_email_idx
+```
+
+Tables created in the same file, or listed in `allowlist`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let constraints: Vec<_> = context
+            .segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_type(SyntaxKind::AlterTableActionSegment))
+            .filter_map(|it| it.child(const { &SyntaxSet::single(SyntaxKind::TableConstraint) }))
+            .filter(builds_unique_index)
+            .collect();
+
+        if constraints.is_empty() || is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        constraints
+            .into_iter()
+            .map(|constraint| LintResult::new(constraint.into(), Vec::new(), None, None))
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AlterTableStatement) })
+            .into()
+    }
+}
+
+/// Whether `constraint` is a unique constraint which builds a new index
+/// rather than using an existing one with `USING INDEX <name>`.
+fn builds_unique_index(constraint: &ErasedSegment) -> bool {
+    let children = code_children(constraint);
+    let uses_existing_index = has_keyword_sequence(&children, &["USING", "INDEX"])
+        && children
+            .iter()
+            .any(|it| it.is_type(SyntaxKind::DatabaseReference));
+
+    children.iter().any(|it| it.is_keyword("UNIQUE")) && !uses_existing_index
+}
@@ -0,0 +1,94 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::{code_children, is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG06 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG06 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG06 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.rename"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renaming tables or columns breaks running clients."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A rename takes effect immediately, so application code which is still deployed and uses the old name fails as soon as the migration commits.
+
+```sql
+ALTER TABLE orders RENAME COLUMN total TO total_amount
+```
+
+**Best practice**
+
+Add the new column, write to both columns while clients migrate, and drop the old column once nothing reads it.
+
+```sql
+ALTER TABLE orders ADD COLUMN total_amount numeric
+```
+
+Tables created in the same file, or listed in `allowlist`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let children = code_children(&context.segment);
+        let Some(idx) = children.iter().position(|it| it.is_keyword("RENAME")) else {
+            return Vec::new();
+        };
+
+        let object = match children.get(idx + 1) {
+            Some(next) if next.is_keyword("TO") => "table",
+            Some(next) if next.is_keyword("CONSTRAINT") => return Vec::new(),
+            Some(_) => "column",
+            None => return Vec::new(),
+        };
+
+        if is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        vec![LintResult::new(
+            children[idx].clone().into(),
+            Vec::new(),
+            Some(format!(
+                "Renaming a {object} breaks clients using the old name."
+            )),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AlterTableStatement) })
+            .into()
+    }
+}
@@ -0,0 +1,99 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use super::{code_children, has_keyword_sequence, is_exempt, NON_POSTGRES_DIALECTS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::safety::Allowlist;
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleMG07 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleMG07 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleMG07 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "migration.set_not_null"
+    }
+
+    fn description(&self) -> &'static str {
+        "'SET NOT NULL' scans the table under an exclusive lock."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`SET NOT NULL` scans every row to check the constraint while holding an `ACCESS EXCLUSIVE` lock, which blocks all access to a large table for the duration.
+
+```sql
+ALTER TABLE orders ALTER COLUMN customer_id SET NOT NULL
+```
+
+**Best practice**
+
+Add a `NOT VALID` check constraint and validate it separately. From postgres 12, `SET NOT NULL` then uses the validated constraint and skips the scan.
+
+```sql
+ALTER TABLE orders
+ADD CONSTRAINT orders_customer_id_not_null CHECK (customer_id IS NOT NULL) NOT VALID;
+
+ALTER TABLE orders VALIDATE CONSTRAINT orders_customer_id_not_null;
+
+ALTER TABLE orders ALTER COLUMN customer_id SET NOT NULL
+```
+
+Tables created in the same file are not checked. Small tables can be listed in `allowlist`.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Migration]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &NON_POSTGRES_DIALECTS
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let actions: Vec<_> = context
+            .segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_type(SyntaxKind::AlterTableActionSegment))
+            .filter(|it| is_set_not_null(it))
+            .cloned()
+            .collect();
+
+        if actions.is_empty() || is_exempt(context, &self.allowlist, &context.segment) {
+            return Vec::new();
+        }
+
+        actions
+            .into_iter()
+            .map(|action| LintResult::new(action.into(), Vec::new(), None, None))
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AlterTableStatement) })
+            .into()
+    }
+}
+
+fn is_set_not_null(action: &ErasedSegment) -> bool {
+    let children = code_children(action);
+    children.first().is_some_and(|it| it.is_keyword("ALTER"))
+        && has_keyword_sequence(&children, &["SET", "NOT", "NULL"])
+}
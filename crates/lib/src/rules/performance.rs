@@ -0,0 +1,21 @@
+use crate::core::rules::base::ErasedRule;
+
+pub mod pf01;
+pub mod pf02;
+pub mod pf03;
+pub mod pf04;
+pub mod pf05;
+pub mod pf06;
+
+pub fn rules() -> Vec<ErasedRule> {
+    use crate::core::rules::base::Erased as _;
+
+    vec![
+        pf01::RulePF01::default().erased(),
+        pf02::RulePF02::default().erased(),
+        pf03::RulePF03::default().erased(),
+        pf04::RulePF04.erased(),
+        pf05::RulePF05::default().erased(),
+        pf06::RulePF06::default().erased(),
+    ]
+}
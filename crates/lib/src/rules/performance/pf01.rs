@@ -0,0 +1,166 @@
+use ahash::{AHashMap, AHashSet};
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RulePF01 {
+    ignore_functions: AHashSet<String>,
+}
+
+impl Rule for RulePF01 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let ignore_functions = config
+            .get("ignore_functions")
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|it| it.as_string().map(|it| it.trim().to_uppercase()))
+            .collect();
+
+        Ok(RulePF01 { ignore_functions }.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.sargable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Functions should not wrap columns used in filter predicates."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Wrapping a filtered column in a function makes the predicate non-sargable. The database has to evaluate the function for every row, so it can't use indexes, partition pruning or clustering on that column.
+
+```sql
+SELECT *
+FROM events
+WHERE DATE(created_at) = '2024-01-01'
+```
+
+**Best practice**
+
+Move the computation to the other side of the comparison so that the column is compared directly. Comparisons with a bare column on either side aren't reported, as that column can still use an index.
+
+```sql
+SELECT *
+FROM events
+WHERE created_at >= '2024-01-01' AND created_at < '2024-01-02'
+```
+
+Functions that are known to be harmless for a given warehouse can be listed in `ignore_functions`.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Performance]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let mut results = Vec::new();
+        let mut stack = vec![context.segment.clone()];
+
+        while let Some(segment) = stack.pop() {
+            for comparison in segment.segments().split(is_boolean_operator) {
+                let operands = comparison.split(is_predicate_operator).collect::<Vec<_>>();
+                // A column compared as it is can still use an index, whatever
+                // the other side is.
+                let is_predicate =
+                    operands.len() > 1 && !operands.iter().any(|operand| is_bare_column(operand));
+
+                for child in comparison {
+                    if is_predicate
+                        && child.is_type(SyntaxKind::Function)
+                        && self.wraps_column(child)
+                    {
+                        results.push(LintResult::new(
+                            child.clone().into(),
+                            Vec::new(),
+                            Some(format!(
+                                "Function '{}' wraps a filtered column, which prevents the use of \
+                             indexes and partition pruning.",
+                                function_name(child)
+                            )),
+                            None,
+                        ));
+                        continue;
+                    }
+
+                    // Subqueries are visited on their own.
+                    if !child.is_type(SyntaxKind::SelectStatement) {
+                        stack.push(child.clone());
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const { SyntaxSet::new(&[SyntaxKind::WhereClause, SyntaxKind::JoinOnCondition]) },
+        )
+        .into()
+    }
+}
+
+impl RulePF01 {
+    fn wraps_column(&self, function: &ErasedSegment) -> bool {
+        if self.ignore_functions.contains(&function_name(function)) {
+            return false;
+        }
+
+        function
+            .segments()
+            .iter()
+            .filter(|it| !it.is_type(SyntaxKind::FunctionName))
+            .any(|it| {
+                !it.recursive_crawl(
+                    const { &SyntaxSet::single(SyntaxKind::ColumnReference) },
+                    true,
+                    const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+                    true,
+                )
+                .is_empty()
+            })
+    }
+}
+
+fn function_name(function: &ErasedSegment) -> String {
+    function
+        .child(const { &SyntaxSet::single(SyntaxKind::FunctionName) })
+        .map(|name| {
+            let raw = name.raw();
+            raw.rsplit('.').next().unwrap_or(raw).to_uppercase()
+        })
+        .unwrap_or_default()
+}
+
+fn is_boolean_operator(segment: &ErasedSegment) -> bool {
+    segment.is_type(SyntaxKind::BinaryOperator)
+        && ["AND", "OR"]
+            .iter()
+            .any(|operator| segment.raw().eq_ignore_ascii_case(operator))
+}
+
+fn is_bare_column(operand: &[ErasedSegment]) -> bool {
+    let mut code = operand.iter().filter(|it| it.is_code());
+    code.next()
+        .is_some_and(|it| it.is_type(SyntaxKind::ColumnReference))
+        && code.next().is_none()
+}
+
+fn is_predicate_operator(segment: &ErasedSegment) -> bool {
+    segment.is_type(SyntaxKind::ComparisonOperator)
+        || ["LIKE", "ILIKE", "RLIKE", "IN", "BETWEEN", "IS"]
+            .iter()
+            .any(|keyword| segment.is_keyword(keyword))
+}
@@ -0,0 +1,116 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Clone)]
+pub struct RulePF02 {
+    pattern_operators: Vec<String>,
+}
+
+impl Default for RulePF02 {
+    fn default() -> Self {
+        Self {
+            pattern_operators: vec!["LIKE".into(), "ILIKE".into()],
+        }
+    }
+}
+
+impl Rule for RulePF02 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let pattern_operators = match config.get("pattern_operators") {
+            Some(value) if !value.is_none() => value
+                .as_array()
+                .ok_or("pattern_operators must be a comma separated list")?
+                .into_iter()
+                .filter_map(|it| it.as_string().map(|it| it.trim().to_uppercase()))
+                .collect(),
+            _ => Self::default().pattern_operators,
+        };
+
+        Ok(RulePF02 { pattern_operators }.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.leading_wildcard"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pattern matches should not start with a wildcard."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A pattern which starts with `%` can't use an index or a sorted layout of the column, so every row has to be scanned.
+
+```sql
+SELECT *
+FROM customers
+WHERE email LIKE '%@example.com'
+```
+
+**Best practice**
+
+Anchor the pattern at the start, or store the searched part in its own column.
+
+```sql
+SELECT *
+FROM customers
+WHERE email_domain = 'example.com'
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Performance]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let mut results = Vec::new();
+        let mut children = context.segment.segments().iter().filter(|it| it.is_code());
+
+        while let Some(child) = children.next() {
+            let is_pattern_operator = child.is_type(SyntaxKind::Keyword)
+                && self
+                    .pattern_operators
+                    .iter()
+                    .any(|operator| child.raw().eq_ignore_ascii_case(operator));
+
+            if !is_pattern_operator {
+                continue;
+            }
+
+            let Some(pattern) = children.next() else {
+                break;
+            };
+
+            if pattern.is_type(SyntaxKind::QuotedLiteral) && has_leading_wildcard(pattern.raw()) {
+                results.push(LintResult::new(
+                    pattern.clone().into(),
+                    Vec::new(),
+                    Some(format!(
+                        "Pattern {} starts with a wildcard and can't use an index.",
+                        pattern.raw()
+                    )),
+                    None,
+                ));
+            }
+        }
+
+        results
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::Expression) }).into()
+    }
+}
+
+fn has_leading_wildcard(raw: &str) -> bool {
+    raw.find(['\'', '"'])
+        .is_some_and(|idx| raw[idx + 1..].starts_with('%'))
+}
@@ -0,0 +1,161 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Clone)]
+pub struct RulePF03 {
+    check_ctes: bool,
+    check_subqueries: bool,
+}
+
+impl Default for RulePF03 {
+    fn default() -> Self {
+        Self {
+            check_ctes: true,
+            check_subqueries: true,
+        }
+    }
+}
+
+impl Rule for RulePF03 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RulePF03 {
+            check_ctes: config
+                .get("check_ctes")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+            check_subqueries: config
+                .get("check_subqueries")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.order_by_without_limit"
+    }
+
+    fn description(&self) -> &'static str {
+        "'ORDER BY' in a CTE or subquery without a 'LIMIT'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Sorting the result of a CTE or subquery is expensive and, unless it is combined with a `LIMIT`, the order is not guaranteed to survive into the outer query.
+
+```sql
+WITH recent AS (
+    SELECT id, created_at
+    FROM orders
+    ORDER BY created_at DESC
+)
+
+SELECT id
+FROM recent
+```
+
+**Best practice**
+
+Only sort in the outermost query, or combine the `ORDER BY` with a `LIMIT`.
+
+```sql
+WITH recent AS (
+    SELECT id, created_at
+    FROM orders
+)
+
+SELECT id
+FROM recent
+ORDER BY created_at DESC
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Performance]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let Some(orderby) = context
+            .segment
+            .child(const { &SyntaxSet::single(SyntaxKind::OrderbyClause) })
+        else {
+            return Vec::new();
+        };
+
+        if has_row_limit(&context.segment) {
+            return Vec::new();
+        }
+
+        let in_cte = context
+            .parent_stack
+            .iter()
+            .any(|it| it.is_type(SyntaxKind::CommonTableExpression));
+        let in_subquery = !in_cte
+            && context.parent_stack.iter().any(|it| {
+                matches!(
+                    it.get_type(),
+                    SyntaxKind::SelectStatement
+                        | SyntaxKind::FromExpressionElement
+                        | SyntaxKind::Expression
+                )
+            });
+
+        let location = if in_cte && self.check_ctes {
+            "CTE"
+        } else if in_subquery && self.check_subqueries {
+            "subquery"
+        } else {
+            return Vec::new();
+        };
+
+        vec![LintResult::new(
+            orderby.into(),
+            Vec::new(),
+            Some(format!(
+                "'ORDER BY' in a {location} without a 'LIMIT' sorts rows that the outer query \
+                 doesn't need ordered."
+            )),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const { SyntaxSet::new(&[SyntaxKind::SelectStatement, SyntaxKind::SetExpression]) },
+        )
+        .into()
+    }
+}
+
+fn has_row_limit(statement: &ErasedSegment) -> bool {
+    if statement
+        .child(const { &SyntaxSet::new(&[SyntaxKind::LimitClause, SyntaxKind::FetchClause]) })
+        .is_some()
+    {
+        return true;
+    }
+
+    // T-SQL style `SELECT TOP n`.
+    statement
+        .child(const { &SyntaxSet::single(SyntaxKind::SelectClause) })
+        .is_some_and(|select_clause| {
+            select_clause
+                .recursive_crawl(
+                    const { &SyntaxSet::single(SyntaxKind::Keyword) },
+                    true,
+                    const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+                    true,
+                )
+                .iter()
+                .any(|keyword| keyword.is_keyword("TOP"))
+        })
+}
@@ -0,0 +1,89 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RulePF04;
+
+impl Rule for RulePF04 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RulePF04.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.distinct_group_by"
+    }
+
+    fn description(&self) -> &'static str {
+        "'SELECT DISTINCT' combined with 'GROUP BY' de-duplicates twice."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`GROUP BY` already produces one row per group, so an additional `DISTINCT` adds a second de-duplication step which most engines execute as another aggregation or sort.
+
+```sql
+SELECT DISTINCT
+    customer_id,
+    COUNT(*) AS orders
+FROM orders
+GROUP BY customer_id
+```
+
+**Best practice**
+
+Drop the `DISTINCT`.
+
+```sql
+SELECT
+    customer_id,
+    COUNT(*) AS orders
+FROM orders
+GROUP BY customer_id
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Performance]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        if context
+            .segment
+            .child(const { &SyntaxSet::single(SyntaxKind::GroupbyClause) })
+            .is_none()
+        {
+            return Vec::new();
+        }
+
+        let distinct = context
+            .segment
+            .child(const { &SyntaxSet::single(SyntaxKind::SelectClause) })
+            .and_then(|select_clause| {
+                select_clause.child(const { &SyntaxSet::single(SyntaxKind::SelectClauseModifier) })
+            })
+            .and_then(|modifier| {
+                modifier
+                    .segments()
+                    .iter()
+                    .find(|it| it.is_keyword("DISTINCT"))
+                    .cloned()
+            });
+
+        match distinct {
+            Some(distinct) => vec![LintResult::new(distinct.into(), Vec::new(), None, None)],
+            None => Vec::new(),
+        }
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::SelectStatement) }).into()
+    }
+}
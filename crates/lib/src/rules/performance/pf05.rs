@@ -0,0 +1,187 @@
+use std::sync::LazyLock;
+
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use strum::IntoEnumIterator;
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Clone)]
+pub struct RulePF05 {
+    max_count_distinct: usize,
+}
+
+impl Default for RulePF05 {
+    fn default() -> Self {
+        Self {
+            max_count_distinct: 1,
+        }
+    }
+}
+
+impl Rule for RulePF05 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let max_count_distinct = match config.get("max_count_distinct") {
+            Some(Value::Int(value)) if *value >= 0 => *value as usize,
+            Some(Value::None) | None => Self::default().max_count_distinct,
+            Some(value) => {
+                return Err(format!(
+                    "max_count_distinct must be a non-negative integer, got {value:?}"
+                ))
+            }
+        };
+
+        Ok(RulePF05 { max_count_distinct }.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.count_distinct"
+    }
+
+    fn description(&self) -> &'static str {
+        "Exact 'COUNT(DISTINCT ...)' over several columns where an approximate function exists."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Every exact `COUNT(DISTINCT ...)` needs its own de-duplication of the input. Counting distinct values of several columns in one query multiplies the memory and shuffle cost.
+
+```sql
+SELECT
+    COUNT(DISTINCT user_id) AS users,
+    COUNT(DISTINCT session_id) AS sessions
+FROM events
+```
+
+**Best practice**
+
+Where an estimate is acceptable, use the approximate distinct count of the dialect.
+
+```sql
+SELECT
+    APPROX_COUNT_DISTINCT(user_id) AS users,
+    APPROX_COUNT_DISTINCT(session_id) AS sessions
+FROM events
+```
+
+The number of exact distinct counts allowed per `SELECT` is set by `max_count_distinct`.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Performance]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        // Dialects without an approximate distinct count.
+        static SKIP: LazyLock<Vec<DialectKind>> = LazyLock::new(|| {
+            DialectKind::iter()
+                .filter(|&dialect| approximate_function(dialect).is_none())
+                .collect()
+        });
+
+        &SKIP
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let Some(replacement) = approximate_function(context.dialect.name) else {
+            return Vec::new();
+        };
+
+        let count_distincts: Vec<_> = context
+            .segment
+            .recursive_crawl(
+                const { &SyntaxSet::single(SyntaxKind::Function) },
+                true,
+                const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+                true,
+            )
+            .into_iter()
+            .filter_map(|function| {
+                let columns = count_distinct_columns(&function)?;
+                Some((function, columns))
+            })
+            .collect();
+
+        let total: usize = count_distincts.iter().map(|(_, columns)| columns).sum();
+        if total <= self.max_count_distinct {
+            return Vec::new();
+        }
+
+        count_distincts
+            .into_iter()
+            .map(|(function, _)| {
+                LintResult::new(
+                    function.into(),
+                    Vec::new(),
+                    Some(format!(
+                        "{total} exact distinct counts in one 'SELECT'. Consider '{replacement}' \
+                         if an estimate is acceptable."
+                    )),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::SelectClause) }).into()
+    }
+}
+
+/// Returns the number of counted columns if `function` is a
+/// `COUNT(DISTINCT ...)`.
+fn count_distinct_columns(function: &ErasedSegment) -> Option<usize> {
+    let name = function.child(const { &SyntaxSet::single(SyntaxKind::FunctionName) })?;
+    if !name.raw().eq_ignore_ascii_case("COUNT") {
+        return None;
+    }
+
+    let arguments = function.child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })?;
+    if !arguments
+        .segments()
+        .iter()
+        .any(|it| it.is_keyword("DISTINCT"))
+    {
+        return None;
+    }
+
+    let columns = arguments
+        .segments()
+        .iter()
+        .filter(|it| it.is_type(SyntaxKind::Expression) || it.is_type(SyntaxKind::Bracketed))
+        .map(|it| {
+            if it.is_type(SyntaxKind::Bracketed) {
+                it.segments()
+                    .iter()
+                    .filter(|it| it.is_type(SyntaxKind::Expression))
+                    .count()
+            } else {
+                1
+            }
+        })
+        .sum::<usize>();
+
+    Some(columns.max(1))
+}
+
+fn approximate_function(dialect: DialectKind) -> Option<&'static str> {
+    match dialect {
+        DialectKind::Athena | DialectKind::Trino => Some("APPROX_DISTINCT"),
+        DialectKind::Bigquery
+        | DialectKind::Databricks
+        | DialectKind::Duckdb
+        | DialectKind::Snowflake
+        | DialectKind::Sparksql => Some("APPROX_COUNT_DISTINCT"),
+        DialectKind::Clickhouse => Some("uniq"),
+        DialectKind::Redshift => Some("APPROXIMATE COUNT(DISTINCT ...)"),
+        DialectKind::Ansi | DialectKind::Postgres | DialectKind::Sqlite => None,
+    }
+}
@@ -0,0 +1,153 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Clone)]
+pub struct RulePF06 {
+    include_distinct: bool,
+}
+
+impl Default for RulePF06 {
+    fn default() -> Self {
+        Self {
+            include_distinct: true,
+        }
+    }
+}
+
+impl Rule for RulePF06 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RulePF06 {
+            include_distinct: config
+                .get("include_distinct")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.union_grouped"
+    }
+
+    fn description(&self) -> &'static str {
+        "'UNION' between grouped queries where 'UNION ALL' would do."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`UNION` de-duplicates the combined result, which requires a sort or hash over every row. When both sides are already grouped, the de-duplication is usually redundant.
+
+```sql
+SELECT region, SUM(amount) AS total
+FROM sales_2023
+GROUP BY region
+UNION
+SELECT region, SUM(amount) AS total
+FROM sales_2024
+GROUP BY region
+```
+
+**Best practice**
+
+Use `UNION ALL`.
+
+```sql
+SELECT region, SUM(amount) AS total
+FROM sales_2023
+GROUP BY region
+UNION ALL
+SELECT region, SUM(amount) AS total
+FROM sales_2024
+GROUP BY region
+```
+
+Queries using `SELECT DISTINCT` count as grouped unless `include_distinct` is disabled.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Performance]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let children: Vec<_> = context
+            .segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_code())
+            .collect();
+
+        let mut results = Vec::new();
+        for (idx, operator) in children.iter().enumerate() {
+            if !operator.is_type(SyntaxKind::SetOperator) || !is_deduplicating_union(operator) {
+                continue;
+            }
+
+            let (Some(lhs), Some(rhs)) = (
+                idx.checked_sub(1).and_then(|idx| children.get(idx)),
+                children.get(idx + 1),
+            ) else {
+                continue;
+            };
+
+            if self.is_grouped(lhs) && self.is_grouped(rhs) {
+                results.push(LintResult::new(
+                    (*operator).clone().into(),
+                    Vec::new(),
+                    None,
+                    None,
+                ));
+            }
+        }
+
+        results
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::SetExpression) }).into()
+    }
+}
+
+impl RulePF06 {
+    fn is_grouped(&self, operand: &ErasedSegment) -> bool {
+        let select = if operand.is_type(SyntaxKind::Bracketed) {
+            match operand.child(const { &SyntaxSet::single(SyntaxKind::SelectStatement) }) {
+                Some(select) => select,
+                None => return false,
+            }
+        } else if operand.is_type(SyntaxKind::SelectStatement) {
+            operand.clone()
+        } else {
+            return false;
+        };
+
+        if select
+            .child(const { &SyntaxSet::single(SyntaxKind::GroupbyClause) })
+            .is_some()
+        {
+            return true;
+        }
+
+        self.include_distinct
+            && select
+                .child(const { &SyntaxSet::single(SyntaxKind::SelectClause) })
+                .and_then(|it| {
+                    it.child(const { &SyntaxSet::single(SyntaxKind::SelectClauseModifier) })
+                })
+                .is_some_and(|it| it.segments().iter().any(|it| it.is_keyword("DISTINCT")))
+    }
+}
+
+fn is_deduplicating_union(operator: &ErasedSegment) -> bool {
+    let keywords = operator.segments();
+    keywords.iter().any(|it| it.is_keyword("UNION"))
+        && !keywords.iter().any(|it| it.is_keyword("ALL"))
+}
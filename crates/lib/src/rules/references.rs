@@ -1,4 +1,13 @@
+use ahash::AHashSet;
+use smol_str::SmolStr;
+use sqruff_lib_core::dialects::syntax::SyntaxKind;
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use sqruff_lib_core::utils::analysis::query::{Query, Selectable};
+use sqruff_lib_core::utils::analysis::select::SelectStatementColumnsAndTables;
+
+use crate::core::catalog::{normalise, Catalog, CatalogTable};
 use crate::core::rules::base::ErasedRule;
+use crate::core::rules::context::RuleContext;
 
 pub mod rf01;
 pub mod rf02;
@@ -6,6 +15,9 @@ pub mod rf03;
 pub mod rf04;
 pub mod rf05;
 pub mod rf06;
+pub mod rf07;
+pub mod rf08;
+pub mod rf09;
 
 pub fn rules() -> Vec<ErasedRule> {
     use crate::core::rules::base::Erased as _;
@@ -17,5 +29,234 @@ pub fn rules() -> Vec<ErasedRule> {
         rf04::RuleRF04::default().erased(),
         rf05::RuleRF05::default().erased(),
         rf06::RuleRF06::default().erased(),
+        rf07::RuleRF07.erased(),
+        rf08::RuleRF08.erased(),
+        rf09::RuleRF09.erased(),
     ]
 }
+
+/// The catalog set by `catalog_path`, if there is one and it loaded.
+pub(crate) fn catalog<'a>(context: &RuleContext<'a>) -> Option<&'a Catalog> {
+    context.config.catalog().ok().flatten()
+}
+
+/// A column reference which doesn't match the catalog.
+pub(crate) enum ColumnIssue {
+    /// No table in scope has the column. `table` is set when the reference
+    /// was qualified.
+    Unknown {
+        reference: ErasedSegment,
+        column: SmolStr,
+        table: Option<SmolStr>,
+    },
+    /// An unqualified column which exists in several tables in scope.
+    Ambiguous {
+        reference: ErasedSegment,
+        column: SmolStr,
+        tables: Vec<SmolStr>,
+    },
+}
+
+/// Resolves every column reference in the query at `context.segment`
+/// against the catalog.
+pub(crate) fn column_issues(context: &RuleContext, catalog: &Catalog) -> Vec<ColumnIssue> {
+    let query: Query<()> = Query::from_segment(&context.segment, context.dialect, None);
+    let mut issues = Vec::new();
+    check_query(&query, catalog, &mut Vec::new(), &mut issues);
+    issues
+}
+
+/// Something a column can be selected from.
+enum Source<'a> {
+    /// A catalog table with known columns.
+    Table {
+        alias: SmolStr,
+        table: &'a CatalogTable,
+    },
+    /// A subquery, CTE or table whose columns aren't known.
+    Opaque { alias: SmolStr },
+}
+
+impl Source<'_> {
+    fn alias(&self) -> &str {
+        match self {
+            Source::Table { alias, .. } | Source::Opaque { alias } => alias,
+        }
+    }
+}
+
+/// The sources in the `FROM` clause of a single `SELECT`.
+struct Scope<'a> {
+    sources: Vec<Source<'a>>,
+}
+
+impl<'a> Scope<'a> {
+    fn is_opaque(&self) -> bool {
+        self.sources
+            .iter()
+            .any(|it| matches!(it, Source::Opaque { .. }))
+    }
+
+    fn tables_with(&self, column: &str) -> Vec<&'a CatalogTable> {
+        self.sources
+            .iter()
+            .filter_map(|source| match source {
+                Source::Table { table, .. } if table.has_column(column) == Some(true) => {
+                    Some(*table)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn check_query<'a>(
+    query: &Query<()>,
+    catalog: &'a Catalog,
+    outer: &mut Vec<Scope<'a>>,
+    issues: &mut Vec<ColumnIssue>,
+) {
+    let selectables = query.inner.borrow().selectables.clone();
+    let mut scopes = Vec::with_capacity(selectables.len());
+
+    for info in selectables.iter().filter_map(|it| it.select_info()) {
+        let scope = scope(query, &info, catalog);
+        check_references(&info, &scope, outer, issues);
+        scopes.push(scope);
+    }
+
+    // Subqueries may refer to the sources of the queries enclosing them,
+    // except for CTEs and subqueries in the `FROM` clause.
+    let depth = outer.len();
+    outer.extend(scopes);
+    for child in query.children() {
+        if is_correlatable(&selectables, &child) {
+            check_query(&child, catalog, outer, issues);
+        } else {
+            check_query(&child, catalog, &mut Vec::new(), issues);
+        }
+    }
+    outer.truncate(depth);
+}
+
+fn is_correlatable(selectables: &[Selectable], child: &Query<()>) -> bool {
+    let child = child.inner.borrow();
+    if child.cte_definition_segment.is_some() {
+        return false;
+    }
+
+    let Some(segment) = child.selectables.first().map(|it| &it.selectable) else {
+        return false;
+    };
+
+    !selectables.iter().any(|selectable| {
+        selectable
+            .selectable
+            .path_to(segment)
+            .iter()
+            .any(|step| step.segment.is_type(SyntaxKind::FromClause))
+    })
+}
+
+fn scope<'a>(
+    query: &Query<()>,
+    info: &SelectStatementColumnsAndTables,
+    catalog: &'a Catalog,
+) -> Scope<'a> {
+    let sources = info
+        .table_aliases
+        .iter()
+        .map(|alias| {
+            let name = normalise(&alias.ref_str);
+            let Some(reference) = &alias.object_reference else {
+                return Source::Opaque { alias: name };
+            };
+
+            let is_cte = !reference.reference().is_qualified()
+                && query.lookup_cte(reference.raw(), false).is_some();
+            match catalog.table(reference.raw()) {
+                Some(table) if !is_cte && table.columns.is_some() => {
+                    Source::Table { alias: name, table }
+                }
+                _ => Source::Opaque { alias: name },
+            }
+        })
+        .collect();
+
+    Scope { sources }
+}
+
+fn check_references(
+    info: &SelectStatementColumnsAndTables,
+    scope: &Scope,
+    outer: &[Scope],
+    issues: &mut Vec<ColumnIssue>,
+) {
+    let aliases: AHashSet<SmolStr> = info
+        .col_aliases
+        .iter()
+        .map(|it| normalise(&it.alias_identifier_name))
+        .chain(info.using_cols.iter().map(|it| normalise(it)))
+        .collect();
+
+    for reference in &info.reference_buffer {
+        if !reference.0.is_type(SyntaxKind::ColumnReference) {
+            continue;
+        }
+
+        let parts = reference.iter_raw_references();
+        let Some((column, qualifiers)) = parts.split_last() else {
+            continue;
+        };
+        let column = SmolStr::new(&column.part);
+
+        if let Some(qualifier) = qualifiers.last() {
+            let qualifier = normalise(&qualifier.part);
+            let source = std::iter::once(scope)
+                .chain(outer.iter().rev())
+                .flat_map(|scope| &scope.sources)
+                .find(|source| source.alias() == qualifier);
+
+            if let Some(Source::Table { table, .. }) = source {
+                if table.has_column(&column) == Some(false) {
+                    issues.push(ColumnIssue::Unknown {
+                        reference: reference.0.clone(),
+                        column,
+                        table: Some(table.name.clone()),
+                    });
+                }
+            }
+
+            continue;
+        }
+
+        if aliases.contains(&normalise(&column)) {
+            continue;
+        }
+
+        let tables = scope.tables_with(&column);
+        if tables.len() > 1 {
+            issues.push(ColumnIssue::Ambiguous {
+                reference: reference.0.clone(),
+                column,
+                tables: tables.iter().map(|it| it.name.clone()).collect(),
+            });
+            continue;
+        }
+
+        if !tables.is_empty() || scope.sources.is_empty() || scope.is_opaque() {
+            continue;
+        }
+
+        let from_outer = outer
+            .iter()
+            .any(|scope| scope.is_opaque() || !scope.tables_with(&column).is_empty());
+        if !from_outer {
+            issues.push(ColumnIssue::Unknown {
+                reference: reference.0.clone(),
+                column,
+                table: None,
+            });
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::catalog;
+use crate::core::catalog::normalise;
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleRF07;
+
+impl Rule for RuleRF07 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleRF07.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "references.unknown_table"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tables should exist in the catalog."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+With `catalog_path` set, a query reads from a table which the catalog doesn't contain, most likely because of a typo.
+
+```sql
+SELECT id
+FROM ordres
+```
+
+**Best practice**
+
+Reference a table from the catalog.
+
+```sql
+SELECT id
+FROM orders
+```
+
+The catalog is either a directory or file of `CREATE TABLE` statements, parsed with the configured dialect, or a JSON or YAML file mapping table names to their columns. This rule does nothing unless `catalog_path` is set.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::References]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let Some(catalog) = catalog(context) else {
+            return Vec::new();
+        };
+
+        let is_source = context.parent_stack.last().is_some_and(|parent| {
+            matches!(
+                parent.get_type(),
+                SyntaxKind::TableExpression
+                    | SyntaxKind::InsertStatement
+                    | SyntaxKind::UpdateStatement
+            )
+        });
+        if !is_source {
+            return Vec::new();
+        }
+
+        let name = context.segment.raw();
+        if catalog.table(name).is_some()
+            || (!context.segment.reference().is_qualified() && is_cte(context, name))
+        {
+            return Vec::new();
+        }
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            Vec::new(),
+            Some(format!("Table '{name}' is not in the catalog.")),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::TableReference) }).into()
+    }
+}
+
+fn is_cte(context: &RuleContext, name: &str) -> bool {
+    let name = normalise(name);
+    context
+        .parent_stack
+        .iter()
+        .filter(|it| it.is_type(SyntaxKind::WithCompoundStatement))
+        .flat_map(|it| {
+            it.children(const { &SyntaxSet::single(SyntaxKind::CommonTableExpression) })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .any(|cte| {
+            cte.segments()
+                .first()
+                .is_some_and(|it| normalise(it.raw()) == name)
+        })
+}
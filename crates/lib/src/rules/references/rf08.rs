@@ -0,0 +1,100 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::{catalog, column_issues, ColumnIssue};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleRF08;
+
+impl Rule for RuleRF08 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleRF08.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "references.unknown_column"
+    }
+
+    fn description(&self) -> &'static str {
+        "Columns should exist in the tables they are selected from."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+With `catalog_path` set, a column is referenced which none of the tables in the `FROM` clause contain. Qualified references are resolved through their table alias.
+
+```sql
+SELECT
+    o.id,
+    o.totl
+FROM orders AS o
+```
+
+**Best practice**
+
+Reference a column which exists.
+
+```sql
+SELECT
+    o.id,
+    o.total
+FROM orders AS o
+```
+
+Columns from subqueries, CTEs and tables whose columns aren't known, such as views, are not checked. This rule does nothing unless `catalog_path` is set.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::References]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let Some(catalog) = catalog(context) else {
+            return Vec::new();
+        };
+
+        column_issues(context, catalog)
+            .into_iter()
+            .filter_map(|issue| match issue {
+                ColumnIssue::Unknown {
+                    reference,
+                    column,
+                    table,
+                } => {
+                    let description = match table {
+                        Some(table) => format!("Column '{column}' is not in table '{table}'."),
+                        None => format!("Column '{column}' is not in any table in scope."),
+                    };
+                    Some(LintResult::new(
+                        reference.into(),
+                        Vec::new(),
+                        Some(description),
+                        None,
+                    ))
+                }
+                ColumnIssue::Ambiguous { .. } => None,
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::WithCompoundStatement,
+                    SyntaxKind::SetExpression,
+                    SyntaxKind::SelectStatement,
+                ])
+            },
+        )
+        .disallow_recurse()
+        .into()
+    }
+}
@@ -0,0 +1,96 @@
+use ahash::AHashMap;
+use itertools::Itertools;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::{catalog, column_issues, ColumnIssue};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleRF09;
+
+impl Rule for RuleRF09 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleRF09.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "references.ambiguous_column"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unqualified columns should exist in only one joined table."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+With `catalog_path` set, an unqualified column exists in more than one of the joined tables. Most engines reject the query, and those which don't pick a table silently.
+
+```sql
+SELECT id
+FROM orders
+INNER JOIN customers ON orders.customer_id = customers.id
+```
+
+**Best practice**
+
+Qualify the column with the table it should come from.
+
+```sql
+SELECT orders.id
+FROM orders
+INNER JOIN customers ON orders.customer_id = customers.id
+```
+
+This rule does nothing unless `catalog_path` is set.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::References]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let Some(catalog) = catalog(context) else {
+            return Vec::new();
+        };
+
+        column_issues(context, catalog)
+            .into_iter()
+            .filter_map(|issue| match issue {
+                ColumnIssue::Ambiguous {
+                    reference,
+                    column,
+                    tables,
+                } => Some(LintResult::new(
+                    reference.into(),
+                    Vec::new(),
+                    Some(format!(
+                        "Column '{column}' is ambiguous, it exists in {}.",
+                        tables.iter().map(|it| format!("'{it}'")).join(" and ")
+                    )),
+                    None,
+                )),
+                ColumnIssue::Unknown { .. } => None,
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::WithCompoundStatement,
+                    SyntaxKind::SetExpression,
+                    SyntaxKind::SelectStatement,
+                ])
+            },
+        )
+        .disallow_recurse()
+        .into()
+    }
+}
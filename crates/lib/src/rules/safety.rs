@@ -0,0 +1,86 @@
+use ahash::{AHashMap, AHashSet};
+use smol_str::{SmolStr, StrExt};
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use crate::core::config::Value;
+use crate::core::rules::base::ErasedRule;
+
+pub mod sf01;
+pub mod sf02;
+pub mod sf03;
+pub mod sf04;
+pub mod sf05;
+pub mod sf06;
+pub mod sf07;
+
+pub fn rules() -> Vec<ErasedRule> {
+    use crate::core::rules::base::Erased as _;
+
+    vec![
+        sf01::RuleSF01::default().erased(),
+        sf02::RuleSF02::default().erased(),
+        sf03::RuleSF03::default().erased(),
+        sf04::RuleSF04::default().erased(),
+        sf05::RuleSF05::default().erased(),
+        sf06::RuleSF06.erased(),
+        sf07::RuleSF07.erased(),
+    ]
+}
+
+/// Object names for which a safety rule doesn't apply.
+///
+/// Names are matched case-insensitively against both the fully qualified
+/// reference and its last part, so `users` allows `app.users` as well.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Allowlist {
+    names: AHashSet<SmolStr>,
+}
+
+impl Allowlist {
+    pub(crate) fn from_config(config: &AHashMap<String, Value>) -> Self {
+        let names = config
+            .get("allowlist")
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|it| it.as_string().map(|it| normalise(it.trim())))
+            .filter(|it| !it.is_empty())
+            .collect();
+
+        Self { names }
+    }
+
+    /// Whether the first object referenced by `statement` is allowlisted.
+    pub(crate) fn allows(&self, statement: &ErasedSegment) -> bool {
+        if self.names.is_empty() {
+            return false;
+        }
+
+        let Some(reference) = target_reference(statement) else {
+            return false;
+        };
+
+        let full = normalise(reference.raw());
+        let last = full.rsplit('.').next().unwrap_or_default();
+        self.names.contains(&full) || self.names.contains(last)
+    }
+}
+
+/// The first object referenced by a statement, ignoring subqueries.
+pub(crate) fn target_reference(statement: &ErasedSegment) -> Option<ErasedSegment> {
+    statement
+        .recursive_crawl(
+            const { &SyntaxSet::new(&[SyntaxKind::TableReference, SyntaxKind::ObjectReference]) },
+            false,
+            const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+            true,
+        )
+        .into_iter()
+        .next()
+}
+
+pub(crate) fn normalise(name: &str) -> SmolStr {
+    name.replace(['"', '`', '[', ']'], "")
+        .to_uppercase_smolstr()
+}
@@ -0,0 +1,88 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::Allowlist;
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF01 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleSF01 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF01 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.unfiltered_dml"
+    }
+
+    fn description(&self) -> &'static str {
+        "'UPDATE' and 'DELETE' statements must have a 'WHERE' clause."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+An `UPDATE` or `DELETE` without a `WHERE` clause changes every row in the table. This is rarely intended and usually the result of a forgotten or truncated filter.
+
+```sql
+DELETE FROM orders
+```
+
+**Best practice**
+
+Always filter the affected rows. Tables which are meant to be cleared completely can be listed in `allowlist`.
+
+```sql
+DELETE FROM orders
+WHERE created_at < '2020-01-01'
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        if context
+            .segment
+            .child(const { &SyntaxSet::single(SyntaxKind::WhereClause) })
+            .is_some()
+            || self.allowlist.allows(&context.segment)
+        {
+            return Vec::new();
+        }
+
+        let statement = if context.segment.is_type(SyntaxKind::UpdateStatement) {
+            "UPDATE"
+        } else {
+            "DELETE"
+        };
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            Vec::new(),
+            Some(format!(
+                "'{statement}' without a 'WHERE' clause affects every row of the table."
+            )),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const { SyntaxSet::new(&[SyntaxKind::UpdateStatement, SyntaxKind::DeleteStatement]) },
+        )
+        .into()
+    }
+}
@@ -0,0 +1,93 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::Allowlist;
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF02 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleSF02 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF02 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.drop_if_exists"
+    }
+
+    fn description(&self) -> &'static str {
+        "'DROP TABLE' and 'DROP SCHEMA' should use 'IF EXISTS'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Dropping an object without `IF EXISTS` fails when the object is missing, which can leave a deployment half applied.
+
+```sql
+DROP TABLE staging_orders
+```
+
+**Best practice**
+
+Guard the drop with `IF EXISTS`.
+
+```sql
+DROP TABLE IF EXISTS staging_orders
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let keywords = context.segment.segments();
+        let has_if_exists = keywords
+            .iter()
+            .filter(|it| it.is_code())
+            .collect::<Vec<_>>()
+            .windows(2)
+            .any(|pair| pair[0].is_keyword("IF") && pair[1].is_keyword("EXISTS"));
+
+        if has_if_exists || self.allowlist.allows(&context.segment) {
+            return Vec::new();
+        }
+
+        let object = if context.segment.is_type(SyntaxKind::DropSchemaStatement) {
+            "SCHEMA"
+        } else {
+            "TABLE"
+        };
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            Vec::new(),
+            Some(format!("'DROP {object}' without 'IF EXISTS'.")),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::DropTableStatement,
+                    SyntaxKind::DropSchemaStatement,
+                ])
+            },
+        )
+        .into()
+    }
+}
@@ -0,0 +1,72 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::Allowlist;
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF03 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleSF03 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF03 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.truncate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Avoid 'TRUNCATE'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`TRUNCATE` removes every row of a table. In most databases it can't be rolled back and doesn't fire delete triggers.
+
+```sql
+TRUNCATE TABLE orders
+```
+
+**Best practice**
+
+Use a filtered `DELETE`, or list tables which are meant to be truncated in `allowlist`.
+
+```sql
+DELETE FROM orders
+WHERE created_at < '2020-01-01'
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        if self.allowlist.allows(&context.segment) {
+            return Vec::new();
+        }
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            Vec::new(),
+            None,
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::TruncateStatement) }).into()
+    }
+}
@@ -0,0 +1,87 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::Allowlist;
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF04 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleSF04 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF04 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.broad_grant"
+    }
+
+    fn description(&self) -> &'static str {
+        "Avoid 'GRANT ALL' and granting to 'PUBLIC'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`GRANT ALL` hands out every privilege, including ones added in future versions, and granting to `PUBLIC` gives access to every role in the database.
+
+```sql
+GRANT ALL ON orders TO analyst;
+GRANT SELECT ON orders TO PUBLIC;
+```
+
+**Best practice**
+
+Grant the specific privileges a named role needs.
+
+```sql
+GRANT SELECT ON orders TO analyst;
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let mut code = context.segment.segments().iter().filter(|it| it.is_code());
+        if !code.next().is_some_and(|it| it.is_keyword("GRANT")) {
+            return Vec::new();
+        }
+
+        let grants_all = code.clone().next().is_some_and(|it| it.is_keyword("ALL"));
+        let mut after_to = code.skip_while(|it| !it.is_keyword("TO")).skip(1);
+        let grants_public = after_to.any(|it| it.raw().eq_ignore_ascii_case("PUBLIC"));
+
+        if !(grants_all || grants_public) || self.allowlist.allows(&context.segment) {
+            return Vec::new();
+        }
+
+        let description = if grants_all {
+            "'GRANT ALL' grants every privilege on the object."
+        } else {
+            "Granting to 'PUBLIC' gives every role access to the object."
+        };
+
+        vec![LintResult::new(
+            context.segment.clone().into(),
+            Vec::new(),
+            Some(description.into()),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::AccessStatement) }).into()
+    }
+}
@@ -0,0 +1,105 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::Allowlist;
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF05 {
+    allowlist: Allowlist,
+}
+
+impl Rule for RuleSF05 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF05 {
+            allowlist: Allowlist::from_config(config),
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.drop_cascade"
+    }
+
+    fn description(&self) -> &'static str {
+        "Avoid 'DROP ... CASCADE'."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`CASCADE` silently drops every object which depends on the dropped one, such as views, foreign keys or functions.
+
+```sql
+DROP TABLE customers CASCADE
+```
+
+**Best practice**
+
+Drop dependent objects explicitly so that the full effect of the statement is visible.
+
+```sql
+DROP VIEW customer_summary;
+DROP TABLE customers;
+```
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let Some(cascade) = context
+            .segment
+            .segments()
+            .iter()
+            .find(|it| it.is_keyword("CASCADE"))
+        else {
+            return Vec::new();
+        };
+
+        if self.allowlist.allows(&context.segment) {
+            return Vec::new();
+        }
+
+        vec![LintResult::new(
+            cascade.clone().into(),
+            Vec::new(),
+            None,
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::DropDatabaseStatement,
+                    SyntaxKind::DropDomainStatement,
+                    SyntaxKind::DropExtensionStatement,
+                    SyntaxKind::DropExternalTableStatement,
+                    SyntaxKind::DropFunctionStatement,
+                    SyntaxKind::DropIndexStatement,
+                    SyntaxKind::DropMaterializedViewStatement,
+                    SyntaxKind::DropObjectStatement,
+                    SyntaxKind::DropOwnedStatement,
+                    SyntaxKind::DropProcedureStatement,
+                    SyntaxKind::DropRoleStatement,
+                    SyntaxKind::DropSchemaStatement,
+                    SyntaxKind::DropSequenceStatement,
+                    SyntaxKind::DropTableStatement,
+                    SyntaxKind::DropTriggerStatement,
+                    SyntaxKind::DropTypeStatement,
+                    SyntaxKind::DropUserStatement,
+                    SyntaxKind::DropViewStatement,
+                ])
+            },
+        )
+        .into()
+    }
+}
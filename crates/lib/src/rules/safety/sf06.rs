@@ -0,0 +1,339 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, SegmentBuilder, Tables};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Object {
+    Table,
+    View,
+    Schema,
+    Index,
+    Function,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Statement {
+    Create(Object),
+    Drop(Object),
+    AddColumn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Guard {
+    IfNotExists,
+    IfExists,
+    OrReplace,
+}
+
+impl Guard {
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Guard::IfNotExists => &["IF", "NOT", "EXISTS"],
+            Guard::IfExists => &["IF", "EXISTS"],
+            Guard::OrReplace => &["OR", "REPLACE"],
+        }
+    }
+}
+
+impl Statement {
+    fn from_kind(kind: SyntaxKind) -> Option<Self> {
+        Some(match kind {
+            SyntaxKind::CreateTableStatement => Statement::Create(Object::Table),
+            SyntaxKind::CreateViewStatement => Statement::Create(Object::View),
+            SyntaxKind::CreateSchemaStatement | SyntaxKind::CreateDatabaseStatement => {
+                Statement::Create(Object::Schema)
+            }
+            SyntaxKind::CreateIndexStatement => Statement::Create(Object::Index),
+            SyntaxKind::CreateFunctionStatement => Statement::Create(Object::Function),
+            // `DROP TABLE` and `DROP SCHEMA` are checked by SF02.
+            SyntaxKind::DropViewStatement => Statement::Drop(Object::View),
+            SyntaxKind::DropDatabaseStatement => Statement::Drop(Object::Schema),
+            SyntaxKind::DropIndexStatement => Statement::Drop(Object::Index),
+            SyntaxKind::DropFunctionStatement => Statement::Drop(Object::Function),
+            _ => return None,
+        })
+    }
+
+    /// The guard which makes the statement idempotent in a dialect, if the
+    /// dialect accepts one. `OR REPLACE` is preferred for views and
+    /// functions, which hold no data, so that their definitions are updated.
+    fn guard(self, dialect: DialectKind) -> Option<Guard> {
+        use DialectKind::*;
+
+        match self {
+            Statement::Create(Object::Table) => Some(Guard::IfNotExists),
+            Statement::Create(Object::View) if dialect == Sqlite => Some(Guard::IfNotExists),
+            Statement::Create(Object::View) => Some(Guard::OrReplace),
+            Statement::Create(Object::Schema) => Some(Guard::IfNotExists),
+            Statement::Create(Object::Index) => {
+                matches!(dialect, Ansi | Bigquery | Clickhouse | Duckdb | Postgres | Sqlite)
+                    .then_some(Guard::IfNotExists)
+            }
+            Statement::Create(Object::Function) => {
+                (!matches!(dialect, Athena | Sqlite)).then_some(Guard::OrReplace)
+            }
+            Statement::Drop(_) => Some(Guard::IfExists),
+            Statement::AddColumn => {
+                matches!(
+                    dialect,
+                    Bigquery | Clickhouse | Duckdb | Postgres | Snowflake | Trino
+                )
+                .then_some(Guard::IfNotExists)
+            }
+        }
+    }
+
+    /// Whether the grammar of a dialect parses the guarded statement, so that
+    /// the guard can be inserted by the fix.
+    fn is_fixable(self, dialect: DialectKind) -> bool {
+        // Snowflake accepts `ADD COLUMN IF NOT EXISTS`, but it isn't parsed
+        // yet.
+        !(self == Statement::AddColumn && dialect == DialectKind::Snowflake)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Statement::Create(Object::Table) => "CREATE TABLE",
+            Statement::Create(Object::View) => "CREATE VIEW",
+            Statement::Create(Object::Schema) => "CREATE SCHEMA",
+            Statement::Create(Object::Index) => "CREATE INDEX",
+            Statement::Create(Object::Function) => "CREATE FUNCTION",
+            Statement::Drop(Object::Table) => "DROP TABLE",
+            Statement::Drop(Object::View) => "DROP VIEW",
+            Statement::Drop(Object::Schema) => "DROP DATABASE",
+            Statement::Drop(Object::Index) => "DROP INDEX",
+            Statement::Drop(Object::Function) => "DROP FUNCTION",
+            Statement::AddColumn => "ADD COLUMN",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF06;
+
+impl Rule for RuleSF06 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF06.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.idempotent_ddl"
+    }
+
+    fn description(&self) -> &'static str {
+        "DDL statements should be idempotent."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A deployment script which creates, drops or alters objects without guarding against their current state fails when it's run a second time, such as after a partial failure.
+
+```sql
+CREATE TABLE orders (id INT);
+CREATE VIEW recent_orders AS SELECT * FROM orders;
+ALTER TABLE orders ADD COLUMN amount INT;
+DROP VIEW staging_orders;
+```
+
+**Best practice**
+
+Use `IF NOT EXISTS`, `OR REPLACE` or `IF EXISTS`.
+
+```sql
+CREATE TABLE IF NOT EXISTS orders (id INT);
+CREATE OR REPLACE VIEW recent_orders AS SELECT * FROM orders;
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS amount INT;
+DROP VIEW IF EXISTS staging_orders;
+```
+
+`CREATE` of tables, views, schemas, indexes and functions, `DROP` of views, databases, indexes and functions, and `ALTER TABLE ... ADD COLUMN`, are checked when the dialect accepts a guard for them. Views and functions are fixed with `OR REPLACE` so that their definitions are updated, other objects with `IF NOT EXISTS`. Temporary tables aren't checked. `DROP TABLE` and `DROP SCHEMA` are left to `safety.drop_if_exists`, which has an allowlist.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let dialect = context.dialect.name;
+        let segment = &context.segment;
+
+        if segment.is_type(SyntaxKind::AlterTableStatement) {
+            return add_columns(segment)
+                .into_iter()
+                .filter_map(|(add, column)| {
+                    violation(context.tables, dialect, Statement::AddColumn, add, Some(column))
+                })
+                .collect();
+        }
+
+        let Some(statement) = Statement::from_kind(segment.get_type()) else {
+            return Vec::new();
+        };
+
+        let keywords = segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_type(SyntaxKind::Keyword))
+            .collect::<Vec<_>>();
+        let guarded = keywords
+            .iter()
+            .any(|it| it.is_keyword("EXISTS") || it.is_keyword("REPLACE"));
+        let temporary = keywords
+            .iter()
+            .any(|it| it.is_keyword("TEMP") || it.is_keyword("TEMPORARY"));
+        if guarded || temporary {
+            return Vec::new();
+        }
+
+        violation(
+            context.tables,
+            dialect,
+            statement,
+            segment.clone(),
+            guard_target(segment),
+        )
+        .into_iter()
+        .collect()
+    }
+
+    fn is_fix_compatible(&self) -> bool {
+        true
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::CreateTableStatement,
+                    SyntaxKind::CreateViewStatement,
+                    SyntaxKind::CreateSchemaStatement,
+                    SyntaxKind::CreateDatabaseStatement,
+                    SyntaxKind::CreateIndexStatement,
+                    SyntaxKind::CreateFunctionStatement,
+                    SyntaxKind::DropViewStatement,
+                    SyntaxKind::DropDatabaseStatement,
+                    SyntaxKind::DropIndexStatement,
+                    SyntaxKind::DropFunctionStatement,
+                    SyntaxKind::AlterTableStatement,
+                ])
+            },
+        )
+        .into()
+    }
+}
+
+/// Reports a statement without a guard, fixing it by inserting the guard
+/// before `target` or, for `OR REPLACE`, after `CREATE`.
+fn violation(
+    tables: &Tables,
+    dialect: DialectKind,
+    statement: Statement,
+    anchor: ErasedSegment,
+    target: Option<ErasedSegment>,
+) -> Option<LintResult> {
+    let guard = statement.guard(dialect)?;
+    // Objects without a name, such as some indexes, can't be guarded.
+    let target = target?;
+
+    let mut fixes = Vec::new();
+    if statement.is_fixable(dialect) {
+        let mut edits = Vec::new();
+        for keyword in guard.keywords() {
+            if guard == Guard::OrReplace {
+                edits.push(SegmentBuilder::whitespace(tables.next_id(), " "));
+                edits.push(SegmentBuilder::keyword(tables.next_id(), keyword));
+            } else {
+                edits.push(SegmentBuilder::keyword(tables.next_id(), keyword));
+                edits.push(SegmentBuilder::whitespace(tables.next_id(), " "));
+            }
+        }
+
+        fixes.push(if guard == Guard::OrReplace {
+            LintFix::create_after(anchor.segments()[0].clone(), edits, None)
+        } else {
+            LintFix::create_before(target, edits)
+        });
+    }
+
+    Some(LintResult::new(
+        anchor.into(),
+        fixes,
+        Some(format!(
+            "'{}' without '{}'.",
+            statement.label(),
+            guard.keywords().join(" ")
+        )),
+        None,
+    ))
+}
+
+/// The name of the object of a `CREATE` or `DROP` statement, which a guard
+/// is inserted before.
+fn guard_target(statement: &ErasedSegment) -> Option<ErasedSegment> {
+    let mut code = statement.segments().iter().filter(|it| it.is_code());
+    code.find(|it| {
+        ["TABLE", "VIEW", "SCHEMA", "DATABASE", "INDEX", "FUNCTION"]
+            .iter()
+            .any(|keyword| it.is_keyword(keyword))
+    })?;
+
+    code.find(|it| !it.is_keyword("CONCURRENTLY"))
+        .filter(|it| !it.is_type(SyntaxKind::Keyword))
+        .cloned()
+}
+
+/// The `ADD` keyword and the column definition of each `ADD COLUMN` action
+/// of an `ALTER TABLE` statement without `IF NOT EXISTS`.
+fn add_columns(statement: &ErasedSegment) -> Vec<(ErasedSegment, ErasedSegment)> {
+    let mut actions = vec![statement.clone()];
+    actions.extend(
+        statement
+            .children(
+                const {
+                    &SyntaxSet::new(&[
+                        SyntaxKind::AlterTableActionSegment,
+                        SyntaxKind::AlterTableTableColumnAction,
+                    ])
+                },
+            )
+            .cloned(),
+    );
+
+    let mut columns = Vec::new();
+    for action in actions {
+        let code = action
+            .segments()
+            .iter()
+            .filter(|it| it.is_code())
+            .collect::<Vec<_>>();
+
+        for (index, add) in code.iter().enumerate() {
+            if !add.is_keyword("ADD") {
+                continue;
+            }
+
+            let mut rest = code[index + 1..].iter();
+            let mut next = rest.next();
+            if next.is_some_and(|it| it.is_keyword("COLUMN")) {
+                next = rest.next();
+            }
+
+            if let Some(column) = next.filter(|it| {
+                it.is_type(SyntaxKind::ColumnReference) || it.is_type(SyntaxKind::ColumnDefinition)
+            }) {
+                columns.push(((*add).clone(), (*column).clone()));
+            }
+        }
+    }
+    columns
+}
@@ -0,0 +1,360 @@
+use ahash::{AHashMap, AHashSet};
+use smol_str::{SmolStr, StrExt};
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, RootOnlyCrawler};
+
+/// `FORMAT` conversions which insert their argument without quoting it.
+const UNQUOTED_CONVERSIONS: &[char] = &['s', 't'];
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSF07;
+
+impl Rule for RuleSF07 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleSF07.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "safety.dynamic_sql"
+    }
+
+    fn description(&self) -> &'static str {
+        "Dynamic SQL should not be built by concatenating values."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+Dynamic SQL is built by concatenating variables into the statement. A value which contains a quote changes the meaning of the statement, which is a SQL injection risk.
+
+```sql
+EXECUTE IMMEDIATE 'SELECT * FROM orders WHERE customer_id = ' || customer_id;
+EXECUTE IMMEDIATE CONCAT('DELETE FROM ', table_name);
+EXECUTE IMMEDIATE FORMAT('SELECT * FROM orders WHERE status = %s', status);
+```
+
+**Best practice**
+
+Pass values as bind parameters with `USING`.
+
+```sql
+EXECUTE IMMEDIATE 'SELECT * FROM orders WHERE customer_id = ?' USING customer_id;
+EXECUTE IMMEDIATE 'DELETE FROM orders WHERE customer_id = ?' USING customer_id;
+EXECUTE IMMEDIATE 'SELECT * FROM orders WHERE status = ?' USING status;
+```
+
+The SQL of BigQuery's and Snowflake's `EXECUTE IMMEDIATE` is checked, including when it's a variable assigned earlier in the file with `SET`, `DECLARE` or `LET`. Operands of `||`, `CONCAT` and of `+` next to a string literal are reported unless they're literals, as are arguments of `FORMAT` inserted with `%s` or `%t`. Postgres' `EXECUTE` runs a prepared statement, and its dynamic SQL is only found in the bodies of functions and `DO` blocks, which are string literals, so it isn't checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Safety]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let statements = context.segment.recursive_crawl(
+            const {
+                &SyntaxSet::new(&[
+                    SyntaxKind::ExecuteImmediateStatement,
+                    SyntaxKind::DeclareSegment,
+                    SyntaxKind::SetSegment,
+                    SyntaxKind::SetStatement,
+                    SyntaxKind::ScriptingLetStatement,
+                ])
+            },
+            true,
+            &SyntaxSet::EMPTY,
+            true,
+        );
+
+        let mut variables: AHashMap<SmolStr, ErasedSegment> = AHashMap::new();
+        let mut reported = AHashSet::new();
+        let mut results = Vec::new();
+        for statement in statements {
+            if !statement.is_type(SyntaxKind::ExecuteImmediateStatement) {
+                if let Some((name, value)) = assignment(&statement) {
+                    variables.insert(name, value);
+                }
+                continue;
+            }
+
+            let Some(sql) = statement
+                .segments()
+                .iter()
+                .find(|it| it.is_type(SyntaxKind::Expression))
+            else {
+                // Snowflake's `EXECUTE IMMEDIATE :variable`.
+                if let Some(value) = statement
+                    .child(const { &SyntaxSet::single(SyntaxKind::Variable) })
+                    .and_then(|it| variables.get(&variable_name(&it)))
+                {
+                    check(value, true, None, &mut results);
+                }
+                continue;
+            };
+
+            match variable(sql).and_then(|it| variables.get(&variable_name(&it))) {
+                Some(value) => check(value, true, None, &mut results),
+                None => check(sql, true, None, &mut results),
+            }
+        }
+
+        // A variable which is executed several times is only reported once.
+        results.retain(|it: &LintResult| {
+            it.anchor
+                .as_ref()
+                .is_none_or(|anchor| reported.insert(anchor.id()))
+        });
+        results
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        RootOnlyCrawler.into()
+    }
+}
+
+/// Reports the operands of `segment` which are inserted into dynamic SQL
+/// without quoting. `top` is set for the SQL itself, which is only reported
+/// when it's built from other values, and `conversion` for the arguments of
+/// `FORMAT`.
+fn check(
+    segment: &ErasedSegment,
+    top: bool,
+    conversion: Option<char>,
+    results: &mut Vec<LintResult>,
+) {
+    let code = segment
+        .segments()
+        .iter()
+        .filter(|it| {
+            it.is_code()
+                && !it.is_type(SyntaxKind::StartBracket)
+                && !it.is_type(SyntaxKind::EndBracket)
+        })
+        .collect::<Vec<_>>();
+
+    match segment.get_type() {
+        SyntaxKind::QuotedLiteral
+        | SyntaxKind::NumericLiteral
+        | SyntaxKind::NullLiteral
+        | SyntaxKind::BooleanLiteral
+        | SyntaxKind::Literal => {}
+        SyntaxKind::Expression | SyntaxKind::Bracketed if code.len() == 1 => {
+            check(code[0], top, conversion, results);
+        }
+        SyntaxKind::Expression if is_concatenation(&code) => {
+            for operand in code
+                .into_iter()
+                .filter(|it| !it.is_type(SyntaxKind::BinaryOperator))
+            {
+                check(operand, false, None, results);
+            }
+        }
+        SyntaxKind::Function => match function_name(segment).as_str() {
+            "CONCAT" => {
+                for argument in arguments(segment) {
+                    check(&argument, false, None, results);
+                }
+            }
+            "FORMAT" => check_format(&arguments(segment), results),
+            _ if !top => unquoted(segment, conversion, results),
+            _ => {}
+        },
+        _ if !top => unquoted(segment, conversion, results),
+        _ => {}
+    }
+}
+
+/// Reports the arguments of a `FORMAT` call which are inserted with a
+/// conversion that doesn't quote them.
+fn check_format(arguments: &[ErasedSegment], results: &mut Vec<LintResult>) {
+    let Some((template, arguments)) = arguments.split_first() else {
+        return;
+    };
+
+    let Some(template) = quoted_literal(template) else {
+        unquoted(template, None, results);
+        return;
+    };
+
+    for (index, conversion) in conversions(template.raw()) {
+        if !UNQUOTED_CONVERSIONS.contains(&conversion) {
+            continue;
+        }
+
+        if let Some(argument) = arguments.get(index) {
+            check(argument, false, Some(conversion), results);
+        }
+    }
+}
+
+/// The conversion character of each placeholder of a `FORMAT` string, with
+/// the index of the argument it's applied to.
+fn conversions(template: &str) -> Vec<(usize, char)> {
+    let mut conversions = Vec::new();
+    let mut chars = template.chars().peekable();
+    let mut next = 0;
+
+    while let Some(char) = chars.next() {
+        if char != '%' {
+            continue;
+        }
+        if chars.next_if_eq(&'%').is_some() {
+            continue;
+        }
+
+        let mut digits = String::new();
+        let mut position = None;
+        loop {
+            match chars.next() {
+                Some(it) if it.is_ascii_digit() => digits.push(it),
+                Some('$') => {
+                    position = digits.parse::<usize>().ok().map(|it| it.saturating_sub(1));
+                    digits.clear();
+                }
+                // A width of `*` is taken from the next argument.
+                Some('*') => {
+                    next += 1;
+                }
+                Some('-' | '+' | ' ' | '#' | '\'' | '.') => {}
+                Some(conversion) => {
+                    let index = position.unwrap_or(next);
+                    conversions.push((index, conversion));
+                    next = index + 1;
+                    break;
+                }
+                None => break,
+            }
+        }
+    }
+
+    conversions
+}
+
+/// Whether an expression joins strings, with `||` or with `+` next to a string
+/// literal.
+fn is_concatenation(code: &[&ErasedSegment]) -> bool {
+    let operators = code
+        .iter()
+        .filter(|it| it.is_type(SyntaxKind::BinaryOperator))
+        .map(|it| it.raw())
+        .collect::<Vec<_>>();
+
+    operators.iter().any(|it| *it == "||")
+        || operators.iter().any(|it| *it == "+")
+            && code.iter().any(|it| it.is_type(SyntaxKind::QuotedLiteral))
+}
+
+fn unquoted(segment: &ErasedSegment, conversion: Option<char>, results: &mut Vec<LintResult>) {
+    let description = match conversion {
+        Some(conversion) => format!(
+            "Non-literal value '{}' is inserted into dynamic SQL with '%{conversion}'.",
+            segment.raw()
+        ),
+        None => format!(
+            "Non-literal value '{}' is concatenated into dynamic SQL.",
+            segment.raw()
+        ),
+    };
+
+    results.push(LintResult::new(
+        segment.clone().into(),
+        Vec::new(),
+        Some(description),
+        None,
+    ));
+}
+
+/// The string literal which an expression consists of.
+fn quoted_literal(segment: &ErasedSegment) -> Option<ErasedSegment> {
+    if segment.is_type(SyntaxKind::QuotedLiteral) {
+        return Some(segment.clone());
+    }
+
+    let mut code = segment.segments().iter().filter(|it| it.is_code());
+    let only = code.next()?;
+    if code.next().is_some() || !segment.is_type(SyntaxKind::Expression) {
+        return None;
+    }
+    quoted_literal(only)
+}
+
+/// The uppercased name of a function.
+fn function_name(function: &ErasedSegment) -> SmolStr {
+    function
+        .child(const { &SyntaxSet::single(SyntaxKind::FunctionName) })
+        .map(|it| it.raw().to_uppercase_smolstr())
+        .unwrap_or_default()
+}
+
+/// The arguments of a function call.
+fn arguments(function: &ErasedSegment) -> Vec<ErasedSegment> {
+    function
+        .child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+        .map(|bracketed| {
+            bracketed
+                .segments()
+                .iter()
+                .filter(|it| {
+                    it.is_code()
+                        && !it.is_type(SyntaxKind::StartBracket)
+                        && !it.is_type(SyntaxKind::EndBracket)
+                        && !it.is_type(SyntaxKind::Comma)
+                })
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The variable which an expression consists of, if it's a single name.
+fn variable(expression: &ErasedSegment) -> Option<ErasedSegment> {
+    let mut code = expression.segments().iter().filter(|it| it.is_code());
+    let only = code.next()?;
+    if code.next().is_some() {
+        return None;
+    }
+
+    match only.get_type() {
+        SyntaxKind::Variable => Some(only.clone()),
+        SyntaxKind::ColumnReference => {
+            let mut parts = only.segments().iter().filter(|it| it.is_code());
+            let name = parts.next()?;
+            parts.next().is_none().then(|| name.clone())
+        }
+        _ => None,
+    }
+}
+
+/// The variable assigned by a `SET`, `DECLARE` or `LET` statement, and the
+/// value assigned to it.
+fn assignment(statement: &ErasedSegment) -> Option<(SmolStr, ErasedSegment)> {
+    let mut code = statement.segments().iter().filter(|it| it.is_code());
+    let name = code.find(|it| !it.is_type(SyntaxKind::Keyword))?;
+    if !name.is_type(SyntaxKind::NakedIdentifier) && !name.is_type(SyntaxKind::Variable) {
+        return None;
+    }
+
+    code.find(|it| {
+        it.is_type(SyntaxKind::AssignmentOperator)
+            || it.is_type(SyntaxKind::ComparisonOperator)
+            || it.is_keyword("DEFAULT")
+    })?;
+    let value = code.next()?;
+
+    Some((variable_name(name), value.clone()))
+}
+
+/// Snowflake refers to variables as `:name` and `$name`.
+fn variable_name(variable: &ErasedSegment) -> SmolStr {
+    variable
+        .raw()
+        .trim_start_matches(['$', ':'])
+        .to_uppercase_smolstr()
+}
@@ -9,6 +9,8 @@ pub mod st06;
 pub mod st07;
 pub mod st08;
 pub mod st09;
+pub mod st10;
+pub mod st11;
 
 pub fn rules() -> Vec<ErasedRule> {
     use crate::core::rules::base::Erased as _;
@@ -23,5 +25,7 @@ pub fn rules() -> Vec<ErasedRule> {
         st07::RuleST07.erased(),
         st08::RuleST08.erased(),
         st09::RuleST09::default().erased(),
+        st10::RuleST10::default().erased(),
+        st11::RuleST11.erased(),
     ]
 }
@@ -0,0 +1,140 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::utils::complexity::StatementMetrics;
+
+type Metric = fn(&StatementMetrics) -> usize;
+
+/// The metrics which have a configurable maximum, by the option setting it.
+const THRESHOLDS: [(&str, &str, Metric); 6] = [
+    ("max_ctes", "Number of common table expressions", |it| {
+        it.ctes
+    }),
+    ("max_subquery_depth", "Subquery nesting depth", |it| {
+        it.subquery_depth
+    }),
+    ("max_joins", "Number of joins", |it| it.joins),
+    ("max_case_branches", "Number of CASE branches", |it| {
+        it.case_branches
+    }),
+    ("max_projection_width", "Projection width", |it| {
+        it.projection_width
+    }),
+    ("max_predicate_complexity", "Predicate complexity", |it| {
+        it.predicate_complexity
+    }),
+];
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleST10 {
+    maximums: [Option<usize>; THRESHOLDS.len()],
+}
+
+impl Rule for RuleST10 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let mut maximums = [None; THRESHOLDS.len()];
+        for ((option, _, _), maximum) in THRESHOLDS.iter().zip(&mut maximums) {
+            *maximum = config
+                .get(*option)
+                .and_then(Value::as_int)
+                .map(|it| it.max(0) as usize);
+        }
+
+        Ok(RuleST10 { maximums }.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "structure.complexity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Statements should not exceed the configured complexity limits."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A single statement has grown so large that it's hard to review, test and change safely, such as one with many joins, deeply nested subqueries or long chains of conditions.
+
+```sql
+SELECT *
+FROM orders
+WHERE customer_id IN (
+    SELECT id
+    FROM customers
+    WHERE region_id IN (
+        SELECT id
+        FROM regions
+        WHERE country_id IN (
+            SELECT id
+            FROM countries
+            WHERE continent = 'EU'
+        )
+    )
+)
+```
+
+**Best practice**
+
+Split the statement up, for example with common table expressions or intermediate models.
+
+```sql
+WITH eu_customers AS (
+    SELECT customers.id
+    FROM customers
+    INNER JOIN regions ON customers.region_id = regions.id
+    INNER JOIN countries ON regions.country_id = countries.id
+    WHERE countries.continent = 'EU'
+)
+
+SELECT orders.*
+FROM orders
+INNER JOIN eu_customers ON orders.customer_id = eu_customers.id
+```
+
+The limits are set with `max_ctes`, `max_subquery_depth`, `max_joins`, `max_case_branches` (counting each `WHEN` and `ELSE`), `max_projection_width` (the columns of the widest `SELECT`) and `max_predicate_complexity` (one more than the number of `AND` and `OR` operators). A limit set to `None` isn't checked.
+
+The same metrics are included in the JSON output of `sqruff lint --format json --metrics`.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Structure]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let metrics = StatementMetrics::new(&context.segment);
+
+        THRESHOLDS
+            .iter()
+            .zip(self.maximums)
+            .filter_map(|((option, label, metric), maximum)| {
+                let maximum = maximum?;
+                let value = metric(&metrics);
+                if value <= maximum {
+                    return None;
+                }
+
+                Some(LintResult::new(
+                    context.segment.clone().into(),
+                    Vec::new(),
+                    Some(format!(
+                        "{label} is {value}, more than the maximum of {maximum} ({option})."
+                    )),
+                    None,
+                ))
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::new(&[SyntaxKind::Statement]) })
+            .disallow_recurse()
+            .into()
+    }
+}
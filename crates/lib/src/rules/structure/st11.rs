@@ -0,0 +1,567 @@
+use std::cell::RefCell;
+
+use ahash::{AHashMap, AHashSet};
+use smol_str::{SmolStr, StrExt};
+use sqruff_lib_core::dialects::base::Dialect;
+use sqruff_lib_core::dialects::common::AliasInfo;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use sqruff_lib_core::parser::segments::select::SelectClauseElementSegment;
+use sqruff_lib_core::utils::analysis::query::Query;
+use sqruff_lib_core::utils::analysis::select::get_select_statement_info;
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleST11;
+
+impl Rule for RuleST11 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleST11.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "structure.unused_projection"
+    }
+
+    fn description(&self) -> &'static str {
+        "Columns selected by a CTE or subquery should be used."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A CTE or subquery selects a column which the query using it never reads. Besides being unnecessary, the column may make the database scan data it doesn't need.
+
+```sql
+WITH orders_per_customer AS (
+    SELECT
+        customer_id,
+        COUNT(*) AS order_count,
+        MAX(ordered_at) AS last_ordered_at
+    FROM orders
+    GROUP BY customer_id
+)
+
+SELECT
+    customers.name,
+    orders_per_customer.order_count
+FROM customers
+INNER JOIN orders_per_customer
+    ON customers.id = orders_per_customer.customer_id
+```
+
+**Best practice**
+
+Remove the unused column.
+
+```sql
+WITH orders_per_customer AS (
+    SELECT
+        customer_id,
+        COUNT(*) AS order_count
+    FROM orders
+    GROUP BY customer_id
+)
+
+SELECT
+    customers.name,
+    orders_per_customer.order_count
+FROM customers
+INNER JOIN orders_per_customer
+    ON customers.id = orders_per_customer.customer_id
+```
+
+A `SELECT *` of the CTE or subquery uses all of its columns. CTEs and subqueries using `SELECT DISTINCT` or set operators such as `UNION` aren't checked, because removing a column would change their results.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Structure]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        if context.segment.is_type(SyntaxKind::WithCompoundStatement) {
+            unused_cte_columns(context)
+        } else {
+            unused_subquery_columns(context)
+        }
+    }
+
+    fn is_fix_compatible(&self) -> bool {
+        true
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::WithCompoundStatement,
+                    SyntaxKind::SelectStatement,
+                ])
+            },
+        )
+        .into()
+    }
+}
+
+/// A query reading the columns of a CTE or subquery, which is known by
+/// `alias` in it.
+struct Consumer {
+    select: ErasedSegment,
+    name: SmolStr,
+    alias: SmolStr,
+    other_aliases: AHashSet<SmolStr>,
+    /// The subquery itself, whose references aren't uses of its columns.
+    source: Option<ErasedSegment>,
+}
+
+fn unused_cte_columns(context: &RuleContext) -> Vec<LintResult> {
+    let Some(query) = Query::<()>::from_root(&context.segment, context.dialect) else {
+        return Vec::new();
+    };
+    let query = RefCell::borrow(&query.inner);
+
+    let references = context.segment.recursive_crawl(
+        const { &SyntaxSet::single(SyntaxKind::TableReference) },
+        true,
+        &SyntaxSet::EMPTY,
+        true,
+    );
+    let selects = context.segment.recursive_crawl(
+        const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+        true,
+        &SyntaxSet::EMPTY,
+        true,
+    );
+
+    let mut results = Vec::new();
+    for cte in query.ctes.values() {
+        let cte = RefCell::borrow(&cte.inner);
+        let (Some(definition), Some(name)) = (&cte.cte_definition_segment, &cte.cte_name_segment)
+        else {
+            continue;
+        };
+
+        // Columns named by a column list are renamed positionally, so they
+        // can't be removed from the body.
+        if definition
+            .child(const { &SyntaxSet::single(SyntaxKind::CTEColumnList) })
+            .is_some()
+        {
+            continue;
+        }
+
+        let [selectable] = cte.selectables.as_slice() else {
+            continue;
+        };
+        if !selectable.selectable.is_type(SyntaxKind::SelectStatement) {
+            continue;
+        }
+
+        let name_key = normalise(name.raw());
+        let uses = references
+            .iter()
+            .filter(|reference| normalise(reference.raw()) == name_key)
+            .collect::<Vec<_>>();
+
+        // Recursive CTEs refer to themselves.
+        if uses.iter().any(|reference| contains(definition, reference)) {
+            continue;
+        }
+
+        let mut consumers = Vec::new();
+        for select in &selects {
+            consumers.extend(consumers_of(select, context.dialect, |alias| {
+                alias
+                    .object_reference
+                    .as_ref()
+                    .is_some_and(|it| normalise(it.raw()) == name_key)
+            }));
+        }
+
+        // A CTE used other than in the FROM clause of a query, for example
+        // by a DML statement, may have any of its columns read.
+        let found = consumers
+            .iter()
+            .map(|(reference, _)| reference.id())
+            .collect::<AHashSet<_>>();
+        if uses.is_empty()
+            || uses
+                .iter()
+                .any(|reference| !found.contains(&reference.id()))
+        {
+            continue;
+        }
+
+        let consumers = consumers
+            .into_iter()
+            .map(|(_, consumer)| consumer)
+            .collect::<Vec<_>>();
+        results.extend(unused_columns(
+            &selectable.selectable,
+            &consumers,
+            &format!("CTE '{}'", name.raw()),
+        ));
+    }
+
+    results
+}
+
+fn unused_subquery_columns(context: &RuleContext) -> Vec<LintResult> {
+    let mut results = Vec::new();
+
+    for (element, mut consumer) in consumers_of(&context.segment, context.dialect, |alias| {
+        alias.object_reference.is_none()
+    }) {
+        let Some(subquery) = element
+            .child(const { &SyntaxSet::single(SyntaxKind::TableExpression) })
+            .and_then(|it| it.child(const { &SyntaxSet::single(SyntaxKind::Bracketed) }))
+            .and_then(|it| it.child(const { &SyntaxSet::single(SyntaxKind::SelectStatement) }))
+        else {
+            continue;
+        };
+
+        consumer.source = Some(subquery.clone());
+        let description = if consumer.name.is_empty() {
+            "subquery".to_owned()
+        } else {
+            format!("subquery '{}'", consumer.name)
+        };
+        results.extend(unused_columns(
+            &subquery,
+            std::slice::from_ref(&consumer),
+            &description,
+        ));
+    }
+
+    results
+}
+
+/// The sources in the FROM clause of `select` matching `predicate`, each with
+/// the segment identifying it: the table reference for tables and CTEs, and
+/// the from expression element otherwise.
+fn consumers_of(
+    select: &ErasedSegment,
+    dialect: &Dialect,
+    predicate: impl Fn(&AliasInfo) -> bool,
+) -> Vec<(ErasedSegment, Consumer)> {
+    let Some(info) = get_select_statement_info(select, Some(dialect), false) else {
+        return Vec::new();
+    };
+
+    info.table_aliases
+        .iter()
+        .filter(|alias| predicate(alias))
+        .map(|alias| {
+            let segment = alias
+                .object_reference
+                .clone()
+                .unwrap_or_else(|| alias.from_expression_element.clone());
+            let consumer = Consumer {
+                select: select.clone(),
+                name: alias.ref_str.clone(),
+                alias: normalise(&alias.ref_str),
+                other_aliases: info
+                    .table_aliases
+                    .iter()
+                    .filter(|it| it.from_expression_element != alias.from_expression_element)
+                    .map(|it| normalise(&it.ref_str))
+                    .collect(),
+                source: None,
+            };
+            (segment, consumer)
+        })
+        .collect()
+}
+
+/// Reports the columns selected by `body` which none of `consumers` read,
+/// with a fix removing them.
+fn unused_columns(
+    body: &ErasedSegment,
+    consumers: &[Consumer],
+    description: &str,
+) -> Vec<LintResult> {
+    if body
+        .child(const { &SyntaxSet::single(SyntaxKind::SelectClause) })
+        .and_then(|it| it.child(const { &SyntaxSet::single(SyntaxKind::SelectClauseModifier) }))
+        .is_some()
+    {
+        return Vec::new();
+    }
+
+    let mut used = AHashSet::new();
+    for consumer in consumers {
+        let Some(used_by) = used_columns(consumer) else {
+            return Vec::new();
+        };
+        used.extend(used_by);
+    }
+    // Aliases may be referred to within the body itself, for example in its
+    // ORDER BY clause or by later columns on some dialects.
+    let referenced_in_body = column_names(body);
+
+    let Some(select_clause) = body.child(const { &SyntaxSet::single(SyntaxKind::SelectClause) })
+    else {
+        return Vec::new();
+    };
+    let children = select_clause.segments();
+    let elements = children
+        .iter()
+        .enumerate()
+        .filter(|(_, it)| it.is_type(SyntaxKind::SelectClauseElement))
+        .map(|(index, it)| (index, projection_name(it)))
+        .collect::<Vec<_>>();
+
+    let unused = elements
+        .iter()
+        .map(|(index, name)| {
+            name.as_ref().is_some_and(|name| {
+                let name = normalise(name);
+                let used_in_body =
+                    is_aliased(&children[*index]) && referenced_in_body.contains(&name);
+                !used.contains(&name) && !used_in_body
+            })
+        })
+        .collect::<Vec<_>>();
+    let Some(first_used) = unused.iter().position(|unused| !unused) else {
+        // Removing every column would leave nothing to select.
+        return Vec::new();
+    };
+
+    let mut fixes = Vec::new();
+    if is_fixable(body) {
+        let mut deleted = AHashSet::new();
+        for (position, (index, _)) in elements.iter().enumerate() {
+            if !unused[position] {
+                continue;
+            }
+
+            // Columns before the first used one are removed with the comma
+            // after them, and the rest with the comma before them.
+            let range = if position < first_used {
+                *index..elements[position + 1].0
+            } else {
+                elements[position - 1].0 + 1..*index + 1
+            };
+            deleted.extend(range);
+        }
+
+        let mut deleted = deleted.into_iter().collect::<Vec<_>>();
+        deleted.sort_unstable();
+
+        if deleted.iter().all(|&index| {
+            let segment = &children[index];
+            segment.is_type(SyntaxKind::SelectClauseElement)
+                || segment.is_type(SyntaxKind::Comma)
+                || segment.is_whitespace()
+                || segment.is_meta()
+        }) {
+            fixes = deleted
+                .into_iter()
+                .filter(|&index| !children[index].is_meta())
+                .map(|index| LintFix::delete(children[index].clone()))
+                .collect();
+        }
+    }
+
+    let mut results = Vec::new();
+    for ((index, name), unused) in elements.into_iter().zip(unused) {
+        if !unused {
+            continue;
+        }
+
+        results.push(LintResult::new(
+            children[index].clone().into(),
+            // The fixes of all the unused columns are attached to the first,
+            // so that they're applied together.
+            std::mem::take(&mut fixes),
+            Some(format!(
+                "Column '{}' of {description} is never used.",
+                name.unwrap()
+            )),
+            None,
+        ));
+    }
+
+    results
+}
+
+/// The names of the columns of a source which `consumer` reads, or `None` if
+/// it reads all of them.
+fn used_columns(consumer: &Consumer) -> Option<AHashSet<SmolStr>> {
+    let select_clause = consumer
+        .select
+        .child(const { &SyntaxSet::single(SyntaxKind::SelectClause) })?;
+    for element in
+        select_clause.children(const { &SyntaxSet::single(SyntaxKind::SelectClauseElement) })
+    {
+        let Some(wildcard) =
+            element.child(const { &SyntaxSet::single(SyntaxKind::WildcardExpression) })
+        else {
+            continue;
+        };
+
+        let raw = wildcard.raw();
+        let qualifier = raw.split_once(".*").map(|(qualifier, _)| qualifier);
+        match qualifier {
+            Some(qualifier) if normalise(qualifier) != consumer.alias => {}
+            _ => return None,
+        }
+    }
+
+    if let Some(from_clause) = consumer
+        .select
+        .child(const { &SyntaxSet::single(SyntaxKind::FromClause) })
+    {
+        let natural = from_clause
+            .recursive_crawl(
+                const { &SyntaxSet::single(SyntaxKind::JoinClause) },
+                true,
+                const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+                true,
+            )
+            .iter()
+            .any(|join| join.segments().iter().any(|it| it.is_keyword("NATURAL")));
+        if natural {
+            return None;
+        }
+    }
+
+    let mut used = AHashSet::new();
+    let mut stack = vec![consumer.select.clone()];
+    while let Some(segment) = stack.pop() {
+        if consumer.source.as_ref() == Some(&segment) {
+            continue;
+        }
+
+        if !matches!(
+            segment.get_type(),
+            SyntaxKind::ColumnReference | SyntaxKind::ObjectReference
+        ) {
+            stack.extend(segment.segments().iter().cloned());
+            continue;
+        }
+
+        let reference = segment;
+        let parts = reference
+            .reference()
+            .iter_raw_references()
+            .into_iter()
+            .map(|part| normalise(&part.part))
+            .collect::<Vec<_>>();
+
+        match parts.as_slice() {
+            // The source itself, such as a whole row passed to a function.
+            [name] if *name == consumer.alias => return None,
+            [qualifier, ..]
+                if parts.len() > 1
+                    && *qualifier != consumer.alias
+                    && consumer.other_aliases.contains(qualifier) => {}
+            _ => used.extend(parts),
+        }
+    }
+
+    // Columns in USING clauses are plain identifiers.
+    used.extend(
+        consumer
+            .select
+            .recursive_crawl(
+                const { &SyntaxSet::single(SyntaxKind::JoinClause) },
+                true,
+                const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+                true,
+            )
+            .iter()
+            .flat_map(|join| {
+                join.children(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+                    .flat_map(|it| it.segments())
+                    .filter(|it| {
+                        it.is_type(SyntaxKind::NakedIdentifier)
+                            || it.is_type(SyntaxKind::QuotedIdentifier)
+                    })
+                    .map(|it| normalise(it.raw()))
+                    .collect::<Vec<_>>()
+            }),
+    );
+
+    Some(used)
+}
+
+fn is_aliased(element: &ErasedSegment) -> bool {
+    SelectClauseElementSegment(element.clone())
+        .alias()
+        .is_some()
+}
+
+/// The names of the columns referred to within `body`.
+fn column_names(body: &ErasedSegment) -> AHashSet<SmolStr> {
+    body.recursive_crawl(
+        const { &SyntaxSet::single(SyntaxKind::ColumnReference) },
+        true,
+        &SyntaxSet::EMPTY,
+        true,
+    )
+    .iter()
+    .flat_map(|reference| {
+        reference
+            .reference()
+            .iter_raw_references()
+            .into_iter()
+            .map(|part| normalise(&part.part))
+    })
+    .collect()
+}
+
+/// The name of the column selected by `element`, if it can be referred to by
+/// name.
+fn projection_name(element: &ErasedSegment) -> Option<SmolStr> {
+    if let Some(alias) = SelectClauseElementSegment(element.clone()).alias() {
+        return Some(alias.alias_identifier_name);
+    }
+
+    let code = element
+        .segments()
+        .iter()
+        .filter(|it| it.is_code())
+        .collect::<Vec<_>>();
+    match code.as_slice() {
+        [reference] if reference.is_type(SyntaxKind::ColumnReference) => reference
+            .reference()
+            .iter_raw_references()
+            .last()
+            .map(|part| part.part.as_str().into()),
+        _ => None,
+    }
+}
+
+/// Whether removing columns leaves the meaning of `body` unchanged, which it
+/// doesn't if it refers to columns by position.
+fn is_fixable(body: &ErasedSegment) -> bool {
+    body.children(
+        const { &SyntaxSet::new(&[SyntaxKind::GroupbyClause, SyntaxKind::OrderbyClause]) },
+    )
+    .all(|clause| {
+        !clause
+            .segments()
+            .iter()
+            .any(|it| it.is_type(SyntaxKind::NumericLiteral))
+    })
+}
+
+fn contains(parent: &ErasedSegment, segment: &ErasedSegment) -> bool {
+    parent
+        .recursive_crawl_all(false)
+        .iter()
+        .any(|it| it == segment)
+}
+
+fn normalise(name: &str) -> SmolStr {
+    name.trim_matches(['"', '`', '[', ']'])
+        .to_uppercase_smolstr()
+}
@@ -0,0 +1,46 @@
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use crate::core::rules::base::ErasedRule;
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+use crate::rules::references::catalog;
+use crate::utils::types::TypeScope;
+
+pub mod ty01;
+pub mod ty02;
+pub mod ty03;
+
+pub fn rules() -> Vec<ErasedRule> {
+    use crate::core::rules::base::Erased as _;
+
+    vec![
+        ty01::RuleTY01.erased(),
+        ty02::RuleTY02.erased(),
+        ty03::RuleTY03.erased(),
+    ]
+}
+
+/// The types of the expressions in the statement at `context.segment`.
+pub(crate) fn type_scope<'a>(context: &RuleContext<'a>) -> TypeScope<'a> {
+    TypeScope::new(
+        &context.segment,
+        &context.parent_stack,
+        catalog(context),
+        context.dialect.name,
+    )
+}
+
+/// Visits each statement whose expressions are checked, including nested
+/// ones.
+pub(crate) fn crawl_statements() -> Crawler {
+    SegmentSeekerCrawler::new(
+        const {
+            SyntaxSet::new(&[
+                SyntaxKind::SelectStatement,
+                SyntaxKind::UpdateStatement,
+                SyntaxKind::DeleteStatement,
+            ])
+        },
+    )
+    .into()
+}
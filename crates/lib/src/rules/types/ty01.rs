@@ -0,0 +1,114 @@
+use ahash::AHashMap;
+
+use super::{crawl_statements, type_scope};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::Crawler;
+use crate::utils::types::{comparisons, string_literal, string_value, Comparison, SqlType};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleTY01;
+
+impl Rule for RuleTY01 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleTY01.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "types.incompatible_comparison"
+    }
+
+    fn description(&self) -> &'static str {
+        "Comparisons should be between values of compatible types."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A value is compared with one of an incompatible type. Depending on the database this is an error at runtime, or the values are silently converted, which can skip indexes or match the wrong rows.
+
+```sql
+SELECT id
+FROM customers
+WHERE phone = 5551234
+```
+
+**Best practice**
+
+Compare values of the same type, casting one of them explicitly if needed.
+
+```sql
+SELECT id
+FROM customers
+WHERE phone = '5551234'
+```
+
+Types are inferred from literals, casts and the return types of known functions. With `catalog_path` set, column types are taken from the catalog as well. String literals may be compared with numbers, booleans, dates and times if they can be converted to them.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Types]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let scope = type_scope(context);
+
+        comparisons(&context.segment)
+            .into_iter()
+            .filter_map(|comparison| {
+                let lhs = scope.infer_sequence(&comparison.lhs)?;
+                let rhs = scope.infer_sequence(&comparison.rhs)?;
+                if is_compatible(&comparison, lhs, rhs) {
+                    return None;
+                }
+
+                Some(LintResult::new(
+                    comparison.operator.into(),
+                    Vec::new(),
+                    Some(format!(
+                        "Comparison between {} and {} values, which are incompatible types.",
+                        lhs.as_ref(),
+                        rhs.as_ref()
+                    )),
+                    None,
+                ))
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        crawl_statements()
+    }
+}
+
+fn is_compatible(comparison: &Comparison, lhs: SqlType, rhs: SqlType) -> bool {
+    if lhs.is_comparable_with(rhs) {
+        return true;
+    }
+
+    if let Some(literal) = string_literal(&comparison.lhs) {
+        return converts_to(string_value(&literal), rhs);
+    }
+
+    if let Some(literal) = string_literal(&comparison.rhs) {
+        return converts_to(string_value(&literal), lhs);
+    }
+
+    false
+}
+
+/// Whether a string literal is implicitly converted to `ty` when compared
+/// with it. Whether dates and times are valid is left to `TY02`.
+fn converts_to(value: &str, ty: SqlType) -> bool {
+    match ty {
+        SqlType::Numeric => value.trim().parse::<f64>().is_ok(),
+        SqlType::Boolean => matches!(
+            value.trim().to_lowercase().as_str(),
+            "true" | "false" | "t" | "f" | "yes" | "no" | "y" | "n" | "on" | "off" | "1" | "0"
+        ),
+        _ => true,
+    }
+}
@@ -0,0 +1,215 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use super::{crawl_statements, type_scope};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::Crawler;
+use crate::utils::types::{
+    comparisons, data_type, function_name, statement_segments, string_literal, string_value,
+    SqlType,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleTY02;
+
+impl Rule for RuleTY02 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleTY02.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "types.invalid_date_literal"
+    }
+
+    fn description(&self) -> &'static str {
+        "Date and time literals should be valid."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+A date, time or timestamp literal is out of range, such as the 13th month or the 30th of February. The query fails at runtime, or on some databases the value is silently adjusted or replaced with `NULL`.
+
+```sql
+SELECT id
+FROM orders
+WHERE created_on = '2024-13-01'
+```
+
+**Best practice**
+
+Use a valid `YYYY-MM-DD` date and `HH:MM:SS` time.
+
+```sql
+SELECT id
+FROM orders
+WHERE created_on = '2024-12-01'
+```
+
+Typed literals such as `DATE '2024-01-01'`, strings cast to dates and times, and strings compared with dates and times are checked. Strings which aren't in ISO 8601 format, such as `'today'`, are not checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Types]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let scope = type_scope(context);
+        let mut literals = Vec::new();
+
+        for segment in
+            std::iter::once(context.segment.clone()).chain(statement_segments(&context.segment))
+        {
+            literals.extend(typed_literal(&segment));
+
+            let children = segment
+                .segments()
+                .iter()
+                .filter(|it| it.is_code())
+                .collect::<Vec<_>>();
+            for pair in children.windows(2) {
+                if pair[0].is_type(SyntaxKind::Keyword)
+                    && pair[1].is_type(SyntaxKind::DateConstructorLiteral)
+                {
+                    if let Some(ty) = SqlType::from_data_type(pair[0].raw()) {
+                        literals.push((pair[1].clone(), ty));
+                    }
+                }
+            }
+        }
+
+        for comparison in comparisons(&context.segment) {
+            for (literal, other) in [
+                (&comparison.lhs, &comparison.rhs),
+                (&comparison.rhs, &comparison.lhs),
+            ] {
+                let Some(literal) = string_literal(literal) else {
+                    continue;
+                };
+
+                if let Some(ty) = scope.infer_sequence(other) {
+                    literals.push((literal, ty));
+                }
+            }
+        }
+
+        literals
+            .into_iter()
+            .filter(|(literal, ty)| ty.is_temporal() && !is_valid(string_value(literal), *ty))
+            .map(|(literal, ty)| {
+                let description = format!("{} is not a valid {}.", literal.raw(), ty.as_ref());
+                LintResult::new(literal.into(), Vec::new(), Some(description), None)
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        crawl_statements()
+    }
+}
+
+/// The literal in `DATE '2024-01-01'`, `CAST('2024-01-01' AS DATE)` or
+/// `'2024-01-01'::DATE`, and the type it's converted to.
+fn typed_literal(segment: &ErasedSegment) -> Option<(ErasedSegment, SqlType)> {
+    match segment.get_type() {
+        SyntaxKind::DatetimeLiteral => {
+            let ty =
+                segment.child(const { &SyntaxSet::single(SyntaxKind::DatetimeTypeIdentifier) })?;
+            let literal = segment.child(const { &SyntaxSet::single(SyntaxKind::QuotedLiteral) })?;
+            Some((literal, SqlType::from_data_type(ty.raw())?))
+        }
+        SyntaxKind::CastExpression => {
+            let operand = segment
+                .segments()
+                .iter()
+                .take_while(|it| !it.is_type(SyntaxKind::CastingOperator))
+                .cloned()
+                .collect::<Vec<_>>();
+            Some((string_literal(&operand)?, data_type(segment)?))
+        }
+        SyntaxKind::Function
+            if matches!(
+                function_name(segment).as_str(),
+                "CAST" | "TRY_CAST" | "SAFE_CAST"
+            ) =>
+        {
+            let bracketed = segment.child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })?;
+            let operand = bracketed
+                .segments()
+                .iter()
+                .filter(|it| it.is_type(SyntaxKind::Expression))
+                .take(1)
+                .cloned()
+                .collect::<Vec<_>>();
+            Some((string_literal(&operand)?, data_type(&bracketed)?))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a literal converted to `ty` is valid. Only literals in ISO 8601
+/// format are checked.
+fn is_valid(value: &str, ty: SqlType) -> bool {
+    let value = value.trim();
+
+    match ty {
+        SqlType::Date | SqlType::Timestamp => match check_date(value) {
+            Some((true, rest)) => rest
+                .strip_prefix(['T', ' '])
+                .and_then(check_time)
+                .map_or(true, |(valid, _)| valid),
+            Some((false, _)) => false,
+            None => true,
+        },
+        SqlType::Time => check_time(value).map_or(true, |(valid, _)| valid),
+        _ => true,
+    }
+}
+
+/// Checks the `YYYY-MM-DD` date at the start of `value`, returning whether
+/// it's valid and the rest of `value`.
+fn check_date(value: &str) -> Option<(bool, &str)> {
+    let (year, rest) = digits(value, 4, 4)?;
+    let (month, rest) = digits(rest.strip_prefix('-')?, 1, 2)?;
+    let (day, rest) = digits(rest.strip_prefix('-')?, 1, 2)?;
+
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+
+    Some((
+        (1..=12).contains(&month) && (1..=days_in_month).contains(&day),
+        rest,
+    ))
+}
+
+/// Checks the `HH:MM[:SS]` time at the start of `value`, returning whether
+/// it's valid and the rest of `value`.
+fn check_time(value: &str) -> Option<(bool, &str)> {
+    let (hour, rest) = digits(value, 1, 2)?;
+    let (minute, mut rest) = digits(rest.strip_prefix(':')?, 2, 2)?;
+    let mut second = 0;
+    if let Some((parsed, after)) = rest.strip_prefix(':').and_then(|it| digits(it, 2, 2)) {
+        (second, rest) = (parsed, after);
+    }
+
+    // Leap seconds are allowed.
+    Some((hour < 24 && minute < 60 && second <= 60, rest))
+}
+
+fn digits(value: &str, min: usize, max: usize) -> Option<(u32, &str)> {
+    let len = value.bytes().take_while(u8::is_ascii_digit).count();
+    if !(min..=max).contains(&len) {
+        return None;
+    }
+
+    Some((value[..len].parse().ok()?, &value[len..]))
+}
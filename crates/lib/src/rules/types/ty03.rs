@@ -0,0 +1,109 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::SyntaxKind;
+
+use super::{crawl_statements, type_scope};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::Crawler;
+use crate::utils::types::{arguments, function_name, statement_segments, SqlType};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleTY03;
+
+impl Rule for RuleTY03 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleTY03.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "types.non_numeric_aggregate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Numeric aggregate functions should be applied to numbers."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`SUM`, `AVG` or a statistical aggregate is applied to a value which isn't a number. Most databases reject the query, and those which don't convert each value at runtime.
+
+```sql
+SELECT SUM(amount_text)
+FROM payments
+```
+
+**Best practice**
+
+Aggregate a numeric column, or cast the value explicitly.
+
+```sql
+SELECT SUM(CAST(amount_text AS DECIMAL(10, 2)))
+FROM payments
+```
+
+Types are inferred as for `TY01`. `SUM` and `AVG` of intervals are allowed.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Types]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let scope = type_scope(context);
+        let mut results = Vec::new();
+
+        for function in statement_segments(&context.segment) {
+            if !function.is_type(SyntaxKind::Function) {
+                continue;
+            }
+
+            let name = function_name(&function);
+            if !matches!(
+                name.as_str(),
+                "SUM"
+                    | "AVG"
+                    | "STDDEV"
+                    | "STDDEV_POP"
+                    | "STDDEV_SAMP"
+                    | "VARIANCE"
+                    | "VAR_POP"
+                    | "VAR_SAMP"
+            ) {
+                continue;
+            }
+
+            let Some(ty) = arguments(&function)
+                .first()
+                .and_then(|argument| scope.infer_sequence(argument))
+            else {
+                continue;
+            };
+
+            if ty == SqlType::Numeric
+                || (ty == SqlType::Interval && matches!(name.as_str(), "SUM" | "AVG"))
+            {
+                continue;
+            }
+
+            results.push(LintResult::new(
+                function.clone().into(),
+                Vec::new(),
+                Some(format!(
+                    "{name} is applied to a {} value, which isn't numeric.",
+                    ty.as_ref()
+                )),
+                None,
+            ));
+        }
+
+        results
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        crawl_statements()
+    }
+}
@@ -0,0 +1,140 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+use crate::core::rules::base::ErasedRule;
+use crate::utils::types::function_name;
+
+pub mod wn01;
+pub mod wn02;
+pub mod wn03;
+pub mod wn04;
+
+pub fn rules() -> Vec<ErasedRule> {
+    use crate::core::rules::base::Erased as _;
+
+    vec![
+        wn01::RuleWN01.erased(),
+        wn02::RuleWN02.erased(),
+        wn03::RuleWN03::default().erased(),
+        wn04::RuleWN04.erased(),
+    ]
+}
+
+/// Window functions which number or look up rows by their position in the
+/// window, ignoring any frame.
+pub(crate) const RANKING_FUNCTIONS: &[&str] = &[
+    "ROW_NUMBER",
+    "RANK",
+    "DENSE_RANK",
+    "PERCENT_RANK",
+    "CUME_DIST",
+    "NTILE",
+    "LAG",
+    "LEAD",
+];
+
+/// Window functions which return a value from a row of the frame.
+pub(crate) const VALUE_FUNCTIONS: &[&str] = &["FIRST_VALUE", "LAST_VALUE", "NTH_VALUE"];
+
+const IDENTIFIERS: SyntaxSet = SyntaxSet::new(&[
+    SyntaxKind::NakedIdentifier,
+    SyntaxKind::QuotedIdentifier,
+    SyntaxKind::Identifier,
+]);
+
+/// A function called with an `OVER` clause.
+pub(crate) struct WindowFunction {
+    pub function: ErasedSegment,
+    pub name: String,
+    pub over: ErasedSegment,
+}
+
+/// The window functions of a select statement, excluding those of nested
+/// select statements.
+pub(crate) fn window_functions(select: &ErasedSegment) -> Vec<WindowFunction> {
+    select
+        .recursive_crawl(
+            const { &SyntaxSet::single(SyntaxKind::Function) },
+            true,
+            const { &SyntaxSet::single(SyntaxKind::SelectStatement) },
+            false,
+        )
+        .into_iter()
+        .filter_map(|function| {
+            let over = function.child(const { &SyntaxSet::single(SyntaxKind::OverClause) })?;
+            Some(WindowFunction {
+                name: function_name(&function),
+                function,
+                over,
+            })
+        })
+        .collect()
+}
+
+/// The windows defined in the `WINDOW` clause of a select statement, by
+/// their uppercased names.
+pub(crate) fn named_windows(select: &ErasedSegment) -> AHashMap<String, ErasedSegment> {
+    let Some(clause) = select.child(const { &SyntaxSet::single(SyntaxKind::NamedWindow) }) else {
+        return AHashMap::new();
+    };
+
+    clause
+        .children(const { &SyntaxSet::single(SyntaxKind::NamedWindowExpression) })
+        .filter_map(|expression| {
+            let name = expression.child(&IDENTIFIERS)?;
+            Some((name.raw().to_uppercase(), expression.clone()))
+        })
+        .collect()
+}
+
+/// The clauses which apply to a window, including those inherited from
+/// named windows.
+#[derive(Debug, Default)]
+pub(crate) struct WindowClauses {
+    pub order_by: bool,
+    pub frame: bool,
+}
+
+/// The clauses which apply to the window of an `OVER` clause, or `None` if
+/// it refers to a window which isn't defined in `select`.
+pub(crate) fn window_clauses(
+    over: &ErasedSegment,
+    select: &ErasedSegment,
+) -> Option<WindowClauses> {
+    let named = named_windows(select);
+    let mut clauses = WindowClauses::default();
+    let mut definition = over.clone();
+
+    // Named windows can refer to each other, but not in a cycle.
+    for _ in 0..=named.len() {
+        let specification = definition
+            .child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+            .and_then(|it| {
+                it.child(const { &SyntaxSet::single(SyntaxKind::WindowSpecification) })
+            });
+        let reference = match &specification {
+            Some(specification) => {
+                clauses.order_by |= specification
+                    .child(const { &SyntaxSet::single(SyntaxKind::OrderbyClause) })
+                    .is_some();
+                clauses.frame |= specification
+                    .child(const { &SyntaxSet::single(SyntaxKind::FrameClause) })
+                    .is_some();
+                specification.child(&IDENTIFIERS)
+            }
+            None if definition.is_type(SyntaxKind::NamedWindowExpression) => definition
+                .children(&IDENTIFIERS)
+                .nth(1)
+                .cloned(),
+            None => definition.child(&IDENTIFIERS),
+        };
+
+        let Some(reference) = reference else {
+            return Some(clauses);
+        };
+        definition = named.get(&reference.raw().to_uppercase())?.clone();
+    }
+
+    None
+}
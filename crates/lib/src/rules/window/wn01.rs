@@ -0,0 +1,86 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::{window_clauses, window_functions, RANKING_FUNCTIONS, VALUE_FUNCTIONS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleWN01;
+
+impl Rule for RuleWN01 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleWN01.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "window.order_by"
+    }
+
+    fn description(&self) -> &'static str {
+        "Ranking and offset window functions should have an ORDER BY."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`ROW_NUMBER`, `RANK`, `LAG`, `LEAD` and similar functions depend on the order of the rows in their window. Without an `ORDER BY`, that order is undefined and the results can change from one run to the next.
+
+```sql
+SELECT
+    customer_id,
+    ROW_NUMBER() OVER (PARTITION BY customer_id) AS order_number
+FROM orders
+```
+
+**Best practice**
+
+Order the window.
+
+```sql
+SELECT
+    customer_id,
+    ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY ordered_at) AS order_number
+FROM orders
+```
+
+`ROW_NUMBER`, `RANK`, `DENSE_RANK`, `PERCENT_RANK`, `CUME_DIST`, `NTILE`, `LAG`, `LEAD`, `FIRST_VALUE`, `LAST_VALUE` and `NTH_VALUE` are checked, including windows defined in a `WINDOW` clause.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Window]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        window_functions(&context.segment)
+            .into_iter()
+            .filter(|it| {
+                RANKING_FUNCTIONS.contains(&it.name.as_str())
+                    || VALUE_FUNCTIONS.contains(&it.name.as_str())
+            })
+            .filter(|it| {
+                window_clauses(&it.over, &context.segment).is_some_and(|clauses| !clauses.order_by)
+            })
+            .map(|it| {
+                LintResult::new(
+                    it.function.into(),
+                    Vec::new(),
+                    Some(format!(
+                        "{} should have an ORDER BY in its window, otherwise its result is \
+                         nondeterministic.",
+                        it.name
+                    )),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::SelectStatement) }).into()
+    }
+}
@@ -0,0 +1,88 @@
+use ahash::AHashMap;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use super::{window_clauses, window_functions, RANKING_FUNCTIONS};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleWN02;
+
+impl Rule for RuleWN02 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleWN02.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "window.implicit_frame"
+    }
+
+    fn description(&self) -> &'static str {
+        "Ordered aggregate windows should have an explicit frame."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+An aggregate or value function over an ordered window without a frame uses the default frame of `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`. This computes a running total rather than a total over the partition, includes every row that ties with the current row, and makes `LAST_VALUE` return the current row.
+
+```sql
+SELECT
+    customer_id,
+    SUM(amount) OVER (PARTITION BY customer_id ORDER BY ordered_at) AS running_total
+FROM orders
+```
+
+**Best practice**
+
+Write the frame explicitly.
+
+```sql
+SELECT
+    customer_id,
+    SUM(amount) OVER (
+        PARTITION BY customer_id
+        ORDER BY ordered_at
+        ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+    ) AS running_total
+FROM orders
+```
+
+Ranking and offset functions such as `ROW_NUMBER` and `LAG` ignore the frame, so they aren't checked.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Window]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        window_functions(&context.segment)
+            .into_iter()
+            .filter(|it| !RANKING_FUNCTIONS.contains(&it.name.as_str()))
+            .filter(|it| {
+                window_clauses(&it.over, &context.segment)
+                    .is_some_and(|clauses| clauses.order_by && !clauses.frame)
+            })
+            .map(|it| {
+                LintResult::new(
+                    it.over.into(),
+                    Vec::new(),
+                    Some(format!(
+                        "The window of {} is ordered without a frame, so it defaults to RANGE \
+                         BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW.",
+                        it.name
+                    )),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::SelectStatement) }).into()
+    }
+}
@@ -0,0 +1,308 @@
+use ahash::AHashMap;
+use itertools::Itertools;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::lint_fix::LintFix;
+use sqruff_lib_core::parser::segments::base::{ErasedSegment, SegmentBuilder};
+
+use super::{named_windows, window_functions};
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Clone)]
+pub struct RuleWN03 {
+    max_identical_windows: usize,
+}
+
+impl Default for RuleWN03 {
+    fn default() -> Self {
+        Self {
+            max_identical_windows: 2,
+        }
+    }
+}
+
+impl Rule for RuleWN03 {
+    fn load_from_config(&self, config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        let max_identical_windows = match config.get("max_identical_windows") {
+            Some(Value::Int(value)) if *value >= 0 => *value as usize,
+            Some(Value::None) | None => Self::default().max_identical_windows,
+            Some(value) => {
+                return Err(format!(
+                    "max_identical_windows must be a non-negative integer, got {value:?}"
+                ))
+            }
+        };
+
+        Ok(RuleWN03 {
+            max_identical_windows,
+        }
+        .erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "window.repeated_spec"
+    }
+
+    fn description(&self) -> &'static str {
+        "Repeated window specifications should be defined once in a WINDOW clause."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+The same window specification is written out for several functions. It's easy for the copies to drift apart when one of them is changed.
+
+```sql
+SELECT
+    SUM(amount) OVER (PARTITION BY customer_id ORDER BY ordered_at) AS total,
+    AVG(amount) OVER (PARTITION BY customer_id ORDER BY ordered_at) AS average,
+    COUNT(*) OVER (PARTITION BY customer_id ORDER BY ordered_at) AS number
+FROM orders
+```
+
+**Best practice**
+
+Define the window once in a `WINDOW` clause and refer to it by name.
+
+```sql
+SELECT
+    SUM(amount) OVER w AS total,
+    AVG(amount) OVER w AS average,
+    COUNT(*) OVER w AS number
+FROM orders
+WINDOW w AS (PARTITION BY customer_id ORDER BY ordered_at)
+```
+
+Specifications are compared ignoring case and whitespace, and a specification is reported when it appears more than `max_identical_windows` times in one `SELECT`. The fix is only applied on dialects which support a `WINDOW` clause, and not to statements with a `QUALIFY` clause.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Window]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let mut specifications: Vec<(String, Vec<ErasedSegment>)> = Vec::new();
+        for function in window_functions(&context.segment) {
+            let Some(bracketed) = function
+                .over
+                .child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+            else {
+                continue;
+            };
+            let key = normalise(&bracketed);
+            if key == "( )" {
+                continue;
+            }
+
+            match specifications.iter_mut().find(|(it, _)| *it == key) {
+                Some((_, overs)) => overs.push(function.over),
+                None => specifications.push((key, vec![function.over])),
+            }
+        }
+
+        let repeated = specifications
+            .into_iter()
+            .filter(|(_, overs)| overs.len() > self.max_identical_windows)
+            .collect_vec();
+        if repeated.is_empty() {
+            return Vec::new();
+        }
+
+        let mut fixes = self.fixes(context, &repeated);
+        repeated
+            .into_iter()
+            .map(|(_, overs)| {
+                let bracketed = overs[0]
+                    .child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+                    .unwrap();
+                LintResult::new(
+                    overs[0].clone().into(),
+                    std::mem::take(&mut fixes),
+                    Some(format!(
+                        "Window specification {} is repeated {} times, define it once in a \
+                         WINDOW clause.",
+                        bracketed.raw(),
+                        overs.len()
+                    )),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn is_fix_compatible(&self) -> bool {
+        true
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(const { SyntaxSet::single(SyntaxKind::SelectStatement) }).into()
+    }
+}
+
+impl RuleWN03 {
+    /// Replaces each repeated specification with a reference to a new named
+    /// window, added to the `WINDOW` clause of the statement.
+    fn fixes(
+        &self,
+        context: &RuleContext,
+        repeated: &[(String, Vec<ErasedSegment>)],
+    ) -> Vec<LintFix> {
+        let select = &context.segment;
+        if !matches!(
+            context.dialect.name,
+            DialectKind::Ansi
+                | DialectKind::Athena
+                | DialectKind::Bigquery
+                | DialectKind::Clickhouse
+                | DialectKind::Databricks
+                | DialectKind::Duckdb
+                | DialectKind::Postgres
+                | DialectKind::Sparksql
+                | DialectKind::Trino
+        ) || select
+            .child(const { &SyntaxSet::single(SyntaxKind::QualifyClause) })
+            .is_some()
+        {
+            return Vec::new();
+        }
+
+        let tables = context.tables;
+        let named = named_windows(select);
+        let mut names = Vec::new();
+        let mut fixes = Vec::new();
+        let mut expressions = Vec::new();
+
+        for (_, overs) in repeated {
+            let name = std::iter::once("w".to_string())
+                .chain((1..).map(|it| format!("w{it}")))
+                .find(|it| !named.contains_key(&it.to_uppercase()) && !names.contains(it))
+                .unwrap();
+
+            let mut bracketed = None;
+            for over in overs {
+                let children = over.segments();
+                let position = children
+                    .iter()
+                    .position(|it| it.is_type(SyntaxKind::Bracketed))
+                    .unwrap();
+
+                let mut edits = Vec::new();
+                if position == 0 || !children[position - 1].is_whitespace() {
+                    edits.push(SegmentBuilder::whitespace(tables.next_id(), " "));
+                }
+                edits.push(
+                    SegmentBuilder::token(tables.next_id(), &name, SyntaxKind::NakedIdentifier)
+                        .finish(),
+                );
+
+                bracketed.get_or_insert_with(|| children[position].clone());
+                fixes.push(LintFix::replace(children[position].clone(), edits, None));
+            }
+
+            if !expressions.is_empty() {
+                expressions.push(SegmentBuilder::comma(tables.next_id()));
+                expressions.push(SegmentBuilder::whitespace(tables.next_id(), " "));
+            }
+            expressions.push(
+                SegmentBuilder::node(
+                    tables.next_id(),
+                    SyntaxKind::NamedWindowExpression,
+                    context.dialect.name,
+                    vec![
+                        SegmentBuilder::token(tables.next_id(), &name, SyntaxKind::NakedIdentifier)
+                            .finish(),
+                        SegmentBuilder::whitespace(tables.next_id(), " "),
+                        SegmentBuilder::keyword(tables.next_id(), "AS"),
+                        SegmentBuilder::whitespace(tables.next_id(), " "),
+                        bracketed.unwrap(),
+                    ],
+                )
+                .finish(),
+            );
+            names.push(name);
+        }
+
+        // Add the new windows to the existing clause, otherwise add a clause
+        // after the last one which must come before it.
+        if let Some(clause) = select.child(const { &SyntaxSet::single(SyntaxKind::NamedWindow) }) {
+            let last = clause
+                .children(const { &SyntaxSet::single(SyntaxKind::NamedWindowExpression) })
+                .last()
+                .unwrap()
+                .clone();
+            let mut edits = vec![
+                SegmentBuilder::comma(tables.next_id()),
+                SegmentBuilder::whitespace(tables.next_id(), " "),
+            ];
+            edits.extend(expressions);
+            fixes.push(LintFix::create_after(last, edits, None));
+        } else {
+            let Some(anchor) = select
+                .children(
+                    const {
+                        &SyntaxSet::new(&[
+                            SyntaxKind::SelectClause,
+                            SyntaxKind::FromClause,
+                            SyntaxKind::WhereClause,
+                            SyntaxKind::GroupbyClause,
+                            SyntaxKind::HavingClause,
+                        ])
+                    },
+                )
+                .last()
+                .cloned()
+            else {
+                return Vec::new();
+            };
+
+            let mut segments = vec![
+                SegmentBuilder::keyword(tables.next_id(), "WINDOW"),
+                SegmentBuilder::whitespace(tables.next_id(), " "),
+            ];
+            segments.extend(expressions);
+            let separator = if select.raw().contains('\n') {
+                SegmentBuilder::newline(tables.next_id(), "\n")
+            } else {
+                SegmentBuilder::whitespace(tables.next_id(), " ")
+            };
+            fixes.push(LintFix::create_after(
+                anchor,
+                vec![
+                    separator,
+                    SegmentBuilder::node(
+                        tables.next_id(),
+                        SyntaxKind::NamedWindow,
+                        context.dialect.name,
+                        segments,
+                    )
+                    .finish(),
+                ],
+                None,
+            ));
+        }
+
+        fixes
+    }
+}
+
+/// The code of a window specification, uppercased apart from quoted
+/// identifiers and with whitespace and comments removed.
+fn normalise(bracketed: &ErasedSegment) -> String {
+    bracketed
+        .get_raw_segments()
+        .into_iter()
+        .filter(|it| it.is_code())
+        .map(|it| {
+            if it.is_type(SyntaxKind::QuotedIdentifier) {
+                it.raw().to_string()
+            } else {
+                it.raw().to_uppercase()
+            }
+        })
+        .join(" ")
+}
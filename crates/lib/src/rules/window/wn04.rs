@@ -0,0 +1,125 @@
+use ahash::AHashMap;
+use itertools::Itertools;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+
+use crate::core::config::Value;
+use crate::core::rules::base::{Erased, ErasedRule, LintResult, Rule, RuleGroups};
+use crate::core::rules::context::RuleContext;
+use crate::core::rules::crawlers::{Crawler, SegmentSeekerCrawler};
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleWN04;
+
+impl Rule for RuleWN04 {
+    fn load_from_config(&self, _config: &AHashMap<String, Value>) -> Result<ErasedRule, String> {
+        Ok(RuleWN04.erased())
+    }
+
+    fn name(&self) -> &'static str {
+        "window.qualify"
+    }
+
+    fn description(&self) -> &'static str {
+        "QUALIFY is not supported by this dialect."
+    }
+
+    fn long_description(&self) -> &'static str {
+        r#"
+**Anti-pattern**
+
+`QUALIFY` filters the results of window functions, but only some databases support it. Elsewhere the query fails, or `QUALIFY` is taken as a table alias.
+
+```sql
+SELECT
+    customer_id,
+    ordered_at
+FROM orders
+QUALIFY ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY ordered_at DESC) = 1
+```
+
+**Best practice**
+
+Filter the window function in an outer query.
+
+```sql
+SELECT
+    customer_id,
+    ordered_at
+FROM (
+    SELECT
+        customer_id,
+        ordered_at,
+        ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY ordered_at DESC) AS row_number
+    FROM orders
+) AS ranked
+WHERE row_number = 1
+```
+
+This rule doesn't apply to the BigQuery, ClickHouse, Databricks, DuckDB, Redshift and Snowflake dialects, which support `QUALIFY`.
+"#
+    }
+
+    fn groups(&self) -> &'static [RuleGroups] {
+        &[RuleGroups::All, RuleGroups::Window]
+    }
+
+    fn dialect_skip(&self) -> &'static [DialectKind] {
+        &[
+            DialectKind::Bigquery,
+            DialectKind::Clickhouse,
+            DialectKind::Databricks,
+            DialectKind::Duckdb,
+            DialectKind::Redshift,
+            DialectKind::Snowflake,
+        ]
+    }
+
+    fn eval(&self, context: &RuleContext) -> Vec<LintResult> {
+        let segment = &context.segment;
+        let qualify = match segment.get_type() {
+            SyntaxKind::QualifyClause => true,
+            // Dialects without a QUALIFY clause either fail to parse it, or
+            // take the keyword as the alias of the preceding table.
+            SyntaxKind::Unparsable => segment
+                .get_raw_segments()
+                .into_iter()
+                .find(|it| it.is_code())
+                .is_some_and(|it| it.raw().eq_ignore_ascii_case("QUALIFY")),
+            SyntaxKind::AliasExpression => segment
+                .segments()
+                .iter()
+                .filter(|it| it.is_code())
+                .exactly_one()
+                .is_ok_and(|it| it.raw().eq_ignore_ascii_case("QUALIFY")),
+            _ => false,
+        };
+
+        if !qualify {
+            return Vec::new();
+        }
+
+        vec![LintResult::new(
+            segment.clone().into(),
+            Vec::new(),
+            Some(format!(
+                "QUALIFY is not supported by the {} dialect.",
+                context.dialect.name.as_ref()
+            )),
+            None,
+        )]
+    }
+
+    fn crawl_behaviour(&self) -> Crawler {
+        SegmentSeekerCrawler::new(
+            const {
+                SyntaxSet::new(&[
+                    SyntaxKind::QualifyClause,
+                    SyntaxKind::Unparsable,
+                    SyntaxKind::AliasExpression,
+                ])
+            },
+        )
+        .into()
+    }
+}
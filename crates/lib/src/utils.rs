@@ -1,3 +1,5 @@
+pub mod complexity;
 pub mod functional;
 pub mod identifers;
 pub mod reflow;
+pub mod types;
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+
+/// Complexity metrics of a single statement.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementMetrics {
+    /// The line the statement starts on.
+    pub line: usize,
+    /// The number of common table expressions.
+    pub ctes: usize,
+    /// How deeply subqueries are nested, zero if there are none.
+    pub subquery_depth: usize,
+    /// The number of joins.
+    pub joins: usize,
+    /// The number of `WHEN` and `ELSE` branches of `CASE` expressions.
+    pub case_branches: usize,
+    /// The largest number of columns selected by a single `SELECT`.
+    pub projection_width: usize,
+    /// One more than the number of `AND` and `OR` operators, in the same way
+    /// that cyclomatic complexity counts one more than the number of
+    /// branches.
+    pub predicate_complexity: usize,
+}
+
+impl StatementMetrics {
+    pub fn new(statement: &ErasedSegment) -> Self {
+        let mut metrics = StatementMetrics {
+            line: statement
+                .get_position_marker()
+                .map_or(0, |marker| marker.line_no()),
+            predicate_complexity: 1,
+            ..StatementMetrics::default()
+        };
+        metrics.visit(statement, 0, false);
+        metrics
+    }
+
+    fn visit(&mut self, segment: &ErasedSegment, mut depth: usize, mut in_query: bool) {
+        match segment.get_type() {
+            SyntaxKind::SelectStatement
+            | SyntaxKind::SetExpression
+            | SyntaxKind::WithCompoundStatement => {
+                if in_query {
+                    depth += 1;
+                    self.subquery_depth = self.subquery_depth.max(depth);
+                }
+            }
+            SyntaxKind::CommonTableExpression => self.ctes += 1,
+            SyntaxKind::JoinClause => self.joins += 1,
+            SyntaxKind::WhenClause | SyntaxKind::ElseClause => self.case_branches += 1,
+            SyntaxKind::SelectClause => {
+                let width = segment
+                    .segments()
+                    .iter()
+                    .filter(|it| it.is_type(SyntaxKind::SelectClauseElement))
+                    .count();
+                self.projection_width = self.projection_width.max(width);
+            }
+            SyntaxKind::BinaryOperator
+                if segment.raw().eq_ignore_ascii_case("AND")
+                    || segment.raw().eq_ignore_ascii_case("OR") =>
+            {
+                self.predicate_complexity += 1;
+            }
+            _ => {}
+        }
+
+        // Queries within the clauses of these statements are subqueries, while
+        // the parts of set expressions and common table expressions aren't.
+        match segment.get_type() {
+            SyntaxKind::SelectStatement
+            | SyntaxKind::UpdateStatement
+            | SyntaxKind::DeleteStatement => in_query = true,
+            SyntaxKind::SetExpression | SyntaxKind::WithCompoundStatement => in_query = false,
+            _ => {}
+        }
+
+        for child in segment.segments() {
+            self.visit(child, depth, in_query);
+        }
+    }
+}
+
+/// The metrics of each statement in a parsed file.
+pub fn file_metrics(tree: &ErasedSegment) -> Vec<StatementMetrics> {
+    tree.recursive_crawl(
+        const { &SyntaxSet::single(SyntaxKind::Statement) },
+        false,
+        &SyntaxSet::EMPTY,
+        true,
+    )
+    .iter()
+    .map(StatementMetrics::new)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use sqruff_lib_core::parser::segments::base::Tables;
+
+    use super::*;
+    use crate::core::config::FluffConfig;
+    use crate::core::linter::core::Linter;
+
+    #[test]
+    fn statement_metrics() {
+        let sql = "WITH a AS (SELECT 1 AS x)
+SELECT
+    x,
+    CASE WHEN x > 1 AND x < 3 THEN 1 WHEN x > 5 THEN 2 ELSE 0 END
+FROM a
+JOIN b ON a.x = b.x OR a.x BETWEEN 1 AND 2
+WHERE x IN (SELECT y FROM (SELECT 2 AS y) AS s);
+SELECT 1;
+";
+        let linter = Linter::new(
+            FluffConfig::new(<_>::default(), None, None),
+            None,
+            None,
+            false,
+        );
+        let tables = Tables::default();
+        let tree = linter
+            .parse_string(&tables, sql, None)
+            .unwrap()
+            .tree
+            .unwrap();
+
+        assert_eq!(
+            file_metrics(&tree),
+            [
+                StatementMetrics {
+                    line: 1,
+                    ctes: 1,
+                    subquery_depth: 2,
+                    joins: 1,
+                    case_branches: 3,
+                    projection_width: 2,
+                    predicate_complexity: 3,
+                },
+                StatementMetrics {
+                    line: 8,
+                    projection_width: 1,
+                    predicate_complexity: 1,
+                    ..StatementMetrics::default()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,558 @@
+use ahash::AHashSet;
+use itertools::Itertools;
+use smol_str::SmolStr;
+use sqruff_lib_core::dialects::init::DialectKind;
+use sqruff_lib_core::dialects::syntax::{SyntaxKind, SyntaxSet};
+use sqruff_lib_core::parser::segments::base::ErasedSegment;
+use sqruff_lib_core::parser::segments::from::FromClauseSegment;
+use strum_macros::AsRefStr;
+
+use crate::core::catalog::{normalise, Catalog, CatalogTable};
+
+/// The broad type of an expression. Types within a family, such as the
+/// different sizes of integer, aren't told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum SqlType {
+    Boolean,
+    Numeric,
+    Text,
+    Date,
+    Time,
+    Timestamp,
+    Interval,
+    Binary,
+    Json,
+}
+
+impl SqlType {
+    /// The type of a declared data type such as `VARCHAR(100)` or
+    /// `timestamp with time zone`, or `None` if it isn't known.
+    pub fn from_data_type(data_type: &str) -> Option<Self> {
+        let data_type = data_type.trim().to_uppercase();
+        let name = data_type
+            .split(|c: char| c == '(' || c == '<' || c.is_whitespace())
+            .next()?;
+
+        Some(match name {
+            "BOOL" | "BOOLEAN" => SqlType::Boolean,
+            "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "BIGINT" | "HUGEINT"
+            | "INT2" | "INT4" | "INT8" | "INT64" | "BYTEINT" | "SMALLSERIAL" | "SERIAL"
+            | "BIGSERIAL" | "DEC" | "DECIMAL" | "NUMERIC" | "NUMBER" | "BIGNUMERIC"
+            | "BIGDECIMAL" | "FLOAT" | "FLOAT4" | "FLOAT8" | "FLOAT64" | "REAL" | "DOUBLE"
+            | "MONEY" => SqlType::Numeric,
+            "CHAR" | "CHARACTER" | "NCHAR" | "BPCHAR" | "VARCHAR" | "VARCHAR2" | "NVARCHAR"
+            | "NVARCHAR2" | "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "STRING"
+            | "CITEXT" | "CLOB" => SqlType::Text,
+            "DATE" => SqlType::Date,
+            "TIME" | "TIMETZ" => SqlType::Time,
+            "DATETIME" | "DATETIME2" | "SMALLDATETIME" | "TIMESTAMP" | "TIMESTAMPTZ"
+            | "TIMESTAMP_NTZ" | "TIMESTAMP_LTZ" | "TIMESTAMP_TZ" => SqlType::Timestamp,
+            "INTERVAL" => SqlType::Interval,
+            "BINARY" | "VARBINARY" | "BYTEA" | "BYTES" | "BLOB" => SqlType::Binary,
+            "JSON" | "JSONB" => SqlType::Json,
+            _ => return None,
+        })
+    }
+
+    pub fn is_temporal(self) -> bool {
+        matches!(self, SqlType::Date | SqlType::Time | SqlType::Timestamp)
+    }
+
+    /// Whether values of the two types can be compared without an explicit
+    /// cast.
+    pub fn is_comparable_with(self, other: SqlType) -> bool {
+        match (self, other) {
+            (SqlType::Date | SqlType::Timestamp, SqlType::Date | SqlType::Timestamp) => true,
+            (SqlType::Text | SqlType::Json, SqlType::Text | SqlType::Json) => true,
+            _ => self == other,
+        }
+    }
+}
+
+/// How the type of a function call follows from its arguments.
+enum Signature {
+    Returns(SqlType),
+    FirstArgument,
+    AnyArgument,
+}
+
+/// Infers the types of expressions in a single statement, from literals,
+/// casts, known function signatures and, with a catalog, column types.
+pub struct TypeScope<'a> {
+    dialect: DialectKind,
+    /// The aliases of the tables selected from, and their catalog entry if
+    /// there is one.
+    sources: Vec<(SmolStr, Option<&'a CatalogTable>)>,
+}
+
+impl<'a> TypeScope<'a> {
+    pub fn new(
+        statement: &ErasedSegment,
+        parent_stack: &[ErasedSegment],
+        catalog: Option<&'a Catalog>,
+        dialect: DialectKind,
+    ) -> Self {
+        let ctes = cte_names(parent_stack);
+        let lookup = |reference: &ErasedSegment| {
+            let is_cte = ctes.contains(&normalise(reference.raw()));
+            catalog
+                .filter(|_| !is_cte)
+                .and_then(|catalog| catalog.table(reference.raw()))
+        };
+
+        let mut sources = Vec::new();
+        for clause in statement.children(const { &SyntaxSet::single(SyntaxKind::FromClause) }) {
+            for (_, alias) in FromClauseSegment(clause.clone()).eventual_aliases() {
+                let table = alias.object_reference.as_ref().and_then(lookup);
+                sources.push((normalise(&alias.ref_str), table));
+            }
+        }
+
+        // UPDATE and DELETE name their target table directly.
+        let children = statement.segments();
+        for (index, reference) in children.iter().enumerate() {
+            if !reference.is_type(SyntaxKind::TableReference) {
+                continue;
+            }
+
+            let alias = children[index + 1..]
+                .iter()
+                .find(|it| it.is_code())
+                .filter(|it| it.is_type(SyntaxKind::AliasExpression))
+                .and_then(|it| identifiers(it).pop())
+                .or_else(|| identifiers(reference).pop())
+                .unwrap_or_default();
+            sources.push((normalise(&alias), lookup(reference)));
+        }
+
+        Self { dialect, sources }
+    }
+
+    /// The type of a single expression segment, if it can be inferred.
+    pub fn infer(&self, segment: &ErasedSegment) -> Option<SqlType> {
+        match segment.get_type() {
+            SyntaxKind::NumericLiteral | SyntaxKind::IntegerLiteral => Some(SqlType::Numeric),
+            SyntaxKind::QuotedLiteral => Some(SqlType::Text),
+            SyntaxKind::BooleanLiteral => Some(SqlType::Boolean),
+            SyntaxKind::IntervalExpression => Some(SqlType::Interval),
+            SyntaxKind::DatetimeLiteral => segment
+                .child(const { &SyntaxSet::single(SyntaxKind::DatetimeTypeIdentifier) })
+                .and_then(|it| SqlType::from_data_type(it.raw())),
+            SyntaxKind::CastExpression => data_type(segment),
+            SyntaxKind::Function => self.function_type(segment),
+            SyntaxKind::BareFunction => {
+                match signature(&segment.raw().to_uppercase(), self.dialect) {
+                    Some(Signature::Returns(ty)) => Some(ty),
+                    _ => None,
+                }
+            }
+            SyntaxKind::ColumnReference => self.column_type(segment),
+            SyntaxKind::Expression | SyntaxKind::Bracketed => {
+                let children = segment
+                    .segments()
+                    .iter()
+                    .filter(|it| {
+                        !matches!(
+                            it.get_type(),
+                            SyntaxKind::StartBracket | SyntaxKind::EndBracket
+                        )
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                self.infer_sequence(&children)
+            }
+            _ => None,
+        }
+    }
+
+    /// The type of a sequence of sibling segments forming one operand, such
+    /// as `price * quantity` or `DATE '2024-01-01'`.
+    pub fn infer_sequence(&self, segments: &[ErasedSegment]) -> Option<SqlType> {
+        let mut segments = segments.iter().filter(|it| it.is_code()).peekable();
+
+        // A leading sign, as in `-amount`.
+        while segments.next_if(|it| is_operator(it)).is_some() {}
+
+        let mut ty = self.infer_operand(segments.next()?, &mut segments)?;
+        while let Some(operator) = segments.next() {
+            if !is_operator(operator) {
+                return None;
+            }
+
+            let operand = self.infer_operand(segments.next()?, &mut segments)?;
+            ty = arithmetic(ty, operator.raw(), operand)?;
+        }
+
+        Some(ty)
+    }
+
+    fn infer_operand<'s>(
+        &self,
+        segment: &ErasedSegment,
+        rest: &mut std::iter::Peekable<impl Iterator<Item = &'s ErasedSegment>>,
+    ) -> Option<SqlType> {
+        // Typed literals such as `DATE '2024-01-01'` are parsed as a keyword
+        // followed by the literal in most dialects.
+        if segment.is_type(SyntaxKind::Keyword) {
+            rest.next_if(|it| it.is_type(SyntaxKind::DateConstructorLiteral))?;
+            return SqlType::from_data_type(segment.raw());
+        }
+
+        self.infer(segment)
+    }
+
+    fn function_type(&self, function: &ErasedSegment) -> Option<SqlType> {
+        let name = function_name(function);
+        if matches!(name.as_str(), "CAST" | "TRY_CAST" | "SAFE_CAST") {
+            return function
+                .child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+                .and_then(|bracketed| data_type(&bracketed));
+        }
+
+        let arguments = arguments(function);
+        match signature(&name, self.dialect)? {
+            Signature::Returns(ty) => Some(ty),
+            Signature::FirstArgument => self.infer_sequence(arguments.first()?),
+            Signature::AnyArgument => arguments
+                .iter()
+                .find_map(|argument| self.infer_sequence(argument)),
+        }
+    }
+
+    fn column_type(&self, reference: &ErasedSegment) -> Option<SqlType> {
+        let parts = identifiers(reference);
+        let (column, qualifiers) = parts.split_last()?;
+
+        let table = match qualifiers.last() {
+            Some(qualifier) => {
+                let qualifier = normalise(qualifier);
+                self.sources
+                    .iter()
+                    .find(|(alias, _)| *alias == qualifier)?
+                    .1?
+            }
+            None => {
+                // Tables whose columns aren't known might have the column too.
+                let mut candidates = self.sources.iter().filter(|(_, table)| {
+                    table.map_or(true, |table| table.has_column(column) != Some(false))
+                });
+                let (_, table) = candidates.next()?;
+                if candidates.next().is_some() {
+                    return None;
+                }
+                (*table)?
+            }
+        };
+
+        SqlType::from_data_type(table.column_type(column)?)
+    }
+}
+
+/// A comparison such as `a = b`, with the code segments on either side of
+/// its operator.
+pub struct Comparison {
+    pub operator: ErasedSegment,
+    pub lhs: Vec<ErasedSegment>,
+    pub rhs: Vec<ErasedSegment>,
+}
+
+/// The comparisons in a statement, excluding those in the statements nested
+/// in it.
+pub fn comparisons(statement: &ErasedSegment) -> Vec<Comparison> {
+    let mut comparisons = Vec::new();
+
+    for segment in statement_segments(statement) {
+        // `SET a = 1` is an assignment rather than a comparison.
+        if segment.is_type(SyntaxKind::SetClause) {
+            continue;
+        }
+
+        let children = segment
+            .segments()
+            .iter()
+            .filter(|it| it.is_code())
+            .cloned()
+            .collect::<Vec<_>>();
+        if !children
+            .iter()
+            .any(|it| it.is_type(SyntaxKind::ComparisonOperator))
+        {
+            continue;
+        }
+
+        for operand in children.split(|it| {
+            it.is_keyword("AND") || it.is_keyword("OR") || it.is_keyword("NOT") || {
+                it.is_type(SyntaxKind::BinaryOperator)
+                    && matches!(it.raw().to_uppercase().as_str(), "AND" | "OR")
+            }
+        }) {
+            let mut operators = operand
+                .iter()
+                .positions(|it| it.is_type(SyntaxKind::ComparisonOperator));
+            let (Some(index), None) = (operators.next(), operators.next()) else {
+                continue;
+            };
+
+            comparisons.push(Comparison {
+                operator: operand[index].clone(),
+                lhs: operand[..index].to_vec(),
+                rhs: operand[index + 1..].to_vec(),
+            });
+        }
+    }
+
+    comparisons
+}
+
+/// The segments within a statement, excluding those of the statements nested
+/// in it, which are checked on their own.
+pub fn statement_segments(statement: &ErasedSegment) -> Vec<ErasedSegment> {
+    let mut segments = Vec::new();
+    let mut stack = vec![statement.clone()];
+
+    while let Some(segment) = stack.pop() {
+        for child in segment.segments().iter().rev() {
+            if !matches!(
+                child.get_type(),
+                SyntaxKind::SelectStatement
+                    | SyntaxKind::SetExpression
+                    | SyntaxKind::WithCompoundStatement
+                    | SyntaxKind::UpdateStatement
+                    | SyntaxKind::DeleteStatement
+                    | SyntaxKind::InsertStatement
+            ) {
+                stack.push(child.clone());
+            }
+        }
+
+        segments.push(segment);
+    }
+
+    // The statement itself comes first.
+    segments.remove(0);
+    segments
+}
+
+/// The uppercased name of a function, without any schema or project prefix.
+pub fn function_name(function: &ErasedSegment) -> String {
+    function
+        .child(const { &SyntaxSet::single(SyntaxKind::FunctionName) })
+        .map(|name| {
+            let raw = name.raw();
+            raw.rsplit('.').next().unwrap_or(raw).to_uppercase()
+        })
+        .unwrap_or_default()
+}
+
+/// The code segments of each argument of a function call, without any
+/// leading `DISTINCT` or `ALL`.
+pub fn arguments(function: &ErasedSegment) -> Vec<Vec<ErasedSegment>> {
+    let Some(bracketed) = function.child(const { &SyntaxSet::single(SyntaxKind::Bracketed) })
+    else {
+        return Vec::new();
+    };
+
+    let segments = bracketed
+        .segments()
+        .iter()
+        .filter(|it| {
+            it.is_code()
+                && !matches!(
+                    it.get_type(),
+                    SyntaxKind::StartBracket | SyntaxKind::EndBracket
+                )
+        })
+        .skip_while(|it| it.is_keyword("DISTINCT") || it.is_keyword("ALL"))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    segments
+        .split(|it| it.is_type(SyntaxKind::Comma))
+        .filter(|it| !it.is_empty())
+        .map(<[_]>::to_vec)
+        .collect()
+}
+
+/// The string literal an operand consists of, if it's nothing else.
+pub fn string_literal(operand: &[ErasedSegment]) -> Option<ErasedSegment> {
+    let mut code = operand.iter().filter(|it| it.is_code());
+    let (Some(segment), None) = (code.next(), code.next()) else {
+        return None;
+    };
+
+    match segment.get_type() {
+        SyntaxKind::QuotedLiteral => Some(segment.clone()),
+        SyntaxKind::Expression => string_literal(segment.segments()),
+        _ => None,
+    }
+}
+
+/// The unquoted value of a string literal.
+pub fn string_value(literal: &ErasedSegment) -> &str {
+    let raw = literal.raw().as_str();
+    // Prefixes such as `E'...'` and `N'...'`.
+    let raw = raw.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    raw.get(1..raw.len().saturating_sub(1)).unwrap_or_default()
+}
+
+/// The type named by the `data_type` child of a cast.
+pub fn data_type(segment: &ErasedSegment) -> Option<SqlType> {
+    segment
+        .child(const { &SyntaxSet::single(SyntaxKind::DataType) })
+        .and_then(|it| SqlType::from_data_type(it.raw()))
+}
+
+fn identifiers(segment: &ErasedSegment) -> Vec<SmolStr> {
+    segment
+        .segments()
+        .iter()
+        .filter(|it| {
+            matches!(
+                it.get_type(),
+                SyntaxKind::NakedIdentifier | SyntaxKind::QuotedIdentifier | SyntaxKind::Identifier
+            )
+        })
+        .map(|it| it.raw().clone())
+        .collect()
+}
+
+fn cte_names(parent_stack: &[ErasedSegment]) -> AHashSet<SmolStr> {
+    parent_stack
+        .iter()
+        .filter(|it| it.is_type(SyntaxKind::WithCompoundStatement))
+        .flat_map(|it| it.children(const { &SyntaxSet::single(SyntaxKind::CommonTableExpression) }))
+        .filter_map(|cte| identifiers(cte).pop())
+        .map(|name| normalise(&name))
+        .collect()
+}
+
+fn is_operator(segment: &ErasedSegment) -> bool {
+    matches!(
+        segment.get_type(),
+        SyntaxKind::BinaryOperator | SyntaxKind::SignIndicator
+    ) && !matches!(segment.raw().to_uppercase().as_str(), "AND" | "OR")
+}
+
+/// The type of `lhs <operator> rhs`.
+fn arithmetic(lhs: SqlType, operator: &str, rhs: SqlType) -> Option<SqlType> {
+    use SqlType::*;
+
+    match (lhs, operator, rhs) {
+        (Json, "||", _) | (_, "||", Json) => Some(Json),
+        (_, "||", _) => Some(Text),
+        (Numeric, "+" | "-" | "*" | "/" | "%", Numeric) => Some(Numeric),
+        (Date | Timestamp, "+" | "-", Interval) => Some(lhs),
+        (Interval, "+", Date | Timestamp) => Some(rhs),
+        (Date, "+" | "-", Numeric) => Some(Date),
+        (Interval, "+" | "-", Interval) => Some(Interval),
+        (Interval, "*" | "/", Numeric) | (Numeric, "*", Interval) => Some(Interval),
+        _ => None,
+    }
+}
+
+/// The signature of a function, taking the dialect's own functions into
+/// account first.
+fn signature(name: &str, dialect: DialectKind) -> Option<Signature> {
+    use DialectKind::*;
+    use Signature::*;
+    use SqlType::*;
+
+    let signature = match (dialect, name) {
+        (Bigquery, "DATE" | "PARSE_DATE" | "DATE_ADD" | "DATE_SUB" | "LAST_DAY") => Returns(Date),
+        (
+            Bigquery,
+            "DATETIME" | "TIMESTAMP" | "PARSE_DATETIME" | "PARSE_TIMESTAMP" | "CURRENT_DATETIME"
+            | "TIMESTAMP_ADD" | "TIMESTAMP_SUB" | "DATETIME_ADD" | "DATETIME_SUB",
+        ) => Returns(Timestamp),
+        (Bigquery, "DATE_DIFF" | "DATETIME_DIFF" | "TIMESTAMP_DIFF") => Returns(Numeric),
+        (Bigquery, "FORMAT_DATE" | "FORMAT_DATETIME" | "FORMAT_TIMESTAMP" | "TO_JSON_STRING") => {
+            Returns(Text)
+        }
+        (Bigquery, "DATE_TRUNC" | "DATETIME_TRUNC" | "TIMESTAMP_TRUNC") => FirstArgument,
+        (Postgres | Redshift, "TO_DATE") => Returns(Date),
+        (
+            Postgres | Redshift,
+            "TO_TIMESTAMP"
+            | "DATE_TRUNC"
+            | "CLOCK_TIMESTAMP"
+            | "STATEMENT_TIMESTAMP"
+            | "TRANSACTION_TIMESTAMP",
+        ) => Returns(Timestamp),
+        (Postgres | Redshift, "TO_CHAR" | "CHR") => Returns(Text),
+        (Postgres | Redshift, "TO_NUMBER") => Returns(Numeric),
+        (Postgres, "AGE") => Returns(Interval),
+        (Redshift, "GETDATE" | "SYSDATE") => Returns(Timestamp),
+        (Redshift, "DATEDIFF" | "LEN") => Returns(Numeric),
+        (Snowflake, "DATE" | "TO_DATE" | "TRY_TO_DATE") => Returns(Date),
+        (
+            Snowflake,
+            "TO_TIMESTAMP" | "TO_TIMESTAMP_NTZ" | "TO_TIMESTAMP_LTZ" | "TO_TIMESTAMP_TZ"
+            | "TRY_TO_TIMESTAMP" | "SYSDATE" | "GETDATE",
+        ) => Returns(Timestamp),
+        (
+            Snowflake,
+            "TO_NUMBER" | "TO_DECIMAL" | "TO_NUMERIC" | "TO_DOUBLE" | "DATEDIFF" | "LEN",
+        ) => Returns(Numeric),
+        (Snowflake, "TO_VARCHAR" | "TO_CHAR") => Returns(Text),
+        (Sparksql | Databricks, "TO_DATE" | "DATE_ADD" | "DATE_SUB") => Returns(Date),
+        (Sparksql | Databricks, "TO_TIMESTAMP") => Returns(Timestamp),
+        (Sparksql | Databricks, "DATEDIFF") => Returns(Numeric),
+        (Sparksql | Databricks, "DATE_FORMAT") => Returns(Text),
+        (Duckdb, "TODAY") => Returns(Date),
+        (Duckdb, "STRPTIME") => Returns(Timestamp),
+        (Duckdb, "STRFTIME") => Returns(Text),
+        // SQLite has no date type; its date functions return text.
+        (Sqlite, "DATE" | "TIME" | "DATETIME" | "STRFTIME") => Returns(Text),
+        (Sqlite, "JULIANDAY") => Returns(Numeric),
+        (Clickhouse, "TODAY" | "TODATE") => Returns(Date),
+        (Clickhouse, "TODATETIME") => Returns(Timestamp),
+        (Clickhouse, "TOSTRING") => Returns(Text),
+        (Trino | Athena, "DATE_PARSE" | "FROM_ISO8601_TIMESTAMP") => Returns(Timestamp),
+        (Trino | Athena, "DATE_DIFF") => Returns(Numeric),
+        (Trino | Athena, "DATE_FORMAT") => Returns(Text),
+        (_, "COUNT" | "AVG" | "STDDEV" | "STDDEV_POP" | "STDDEV_SAMP" | "VARIANCE")
+        | (_, "VAR_POP" | "VAR_SAMP" | "ABS" | "ROUND" | "FLOOR" | "CEIL" | "CEILING")
+        | (_, "MOD" | "POWER" | "SQRT" | "EXP" | "LN" | "LOG" | "SIGN" | "LENGTH")
+        | (_, "CHAR_LENGTH" | "CHARACTER_LENGTH" | "OCTET_LENGTH" | "POSITION" | "STRPOS")
+        | (_, "EXTRACT" | "DATE_PART" | "ROW_NUMBER" | "RANK" | "DENSE_RANK" | "NTILE") => {
+            Returns(Numeric)
+        }
+        (_, "LOWER" | "UPPER" | "TRIM" | "LTRIM" | "RTRIM" | "BTRIM" | "CONCAT" | "CONCAT_WS")
+        | (_, "SUBSTRING" | "SUBSTR" | "REPLACE" | "LPAD" | "RPAD" | "LEFT" | "RIGHT")
+        | (_, "INITCAP" | "REVERSE" | "REPEAT" | "MD5" | "TRANSLATE" | "SPLIT_PART")
+        | (_, "STRING_AGG" | "LISTAGG" | "GROUP_CONCAT") => Returns(Text),
+        (_, "CURRENT_DATE") => Returns(Date),
+        (_, "CURRENT_TIME" | "LOCALTIME") => Returns(Time),
+        (_, "CURRENT_TIMESTAMP" | "LOCALTIMESTAMP" | "NOW") => Returns(Timestamp),
+        (_, "SUM" | "MIN" | "MAX" | "ANY_VALUE" | "NULLIF" | "FIRST_VALUE" | "LAST_VALUE")
+        | (_, "LAG" | "LEAD") => FirstArgument,
+        (_, "COALESCE" | "IFNULL" | "NVL" | "GREATEST" | "LEAST") => AnyArgument,
+        _ => return None,
+    };
+
+    Some(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqlType;
+
+    #[test]
+    fn data_types() {
+        assert_eq!(SqlType::from_data_type("int"), Some(SqlType::Numeric));
+        assert_eq!(
+            SqlType::from_data_type("double precision"),
+            Some(SqlType::Numeric)
+        );
+        assert_eq!(SqlType::from_data_type("VARCHAR(100)"), Some(SqlType::Text));
+        assert_eq!(
+            SqlType::from_data_type("timestamp with time zone"),
+            Some(SqlType::Timestamp)
+        );
+        assert_eq!(SqlType::from_data_type("TIME"), Some(SqlType::Time));
+        assert_eq!(SqlType::from_data_type("INT[]"), None);
+        assert_eq!(SqlType::from_data_type("ARRAY<INT64>"), None);
+        assert_eq!(SqlType::from_data_type("uuid"), None);
+    }
+}
@@ -0,0 +1,5 @@
+CREATE TABLE app.customers (
+    id INT PRIMARY KEY,
+    name VARCHAR(100),
+    email VARCHAR(255)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    customer_id INT NOT NULL,
+    total NUMERIC(12, 2),
+    created_at TIMESTAMP
+);
+
+CREATE VIEW recent_orders AS
+SELECT * FROM orders;
@@ -0,0 +1,3 @@
+CREATE TABLE orders (id INT);
+
+CREATE TABLE customers (id INT,, name TEXT);
@@ -15,6 +15,7 @@ use lsp_types::{
 use serde_json::Value;
 use sqruff_lib::core::config::FluffConfig;
 use sqruff_lib::core::linter::core::Linter;
+use sqruff_lib_core::errors::Severity;
 use wasm_bindgen::prelude::*;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -202,14 +203,21 @@ impl LanguageServer {
 
                 let code = violation
                     .rule
+                    .as_ref()
                     .map(|rule| NumberOrString::String(rule.code.to_string()));
 
+                let severity = match violation.severity {
+                    Severity::Error => DiagnosticSeverity::ERROR,
+                    Severity::Warning => DiagnosticSeverity::WARNING,
+                    Severity::Info => DiagnosticSeverity::INFORMATION,
+                };
+
                 Diagnostic::new(
                     range,
-                    DiagnosticSeverity::WARNING.into(),
+                    severity.into(),
                     code,
                     Some("sqruff".to_string()),
-                    violation.description,
+                    violation.message().into_owned(),
                     None,
                     None,
                 )